@@ -2,12 +2,23 @@ use std::ops::Deref;
 
 pub struct Error {
     msg: String,
+    exit_code: Option<i32>,
 }
 
 impl Error {
     pub fn new(msg: &str) -> Self {
         Self {
             msg: msg.to_string(),
+            exit_code: None,
+        }
+    }
+
+    /// an error that cproj should exit with a specific code for, such as when
+    /// the project binary fails and its exit code needs to be passed on
+    pub fn with_exit_code(msg: &str, exit_code: i32) -> Self {
+        Self {
+            msg: msg.to_string(),
+            exit_code: Some(exit_code),
         }
     }
 
@@ -15,6 +26,10 @@ impl Error {
         &self.msg
     }
 
+    pub fn get_exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+
     pub fn prepend(mut self, prefix: &str) -> Self {
         self.msg = prefix.to_string() + self.get_msg();
         self
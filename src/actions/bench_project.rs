@@ -0,0 +1,111 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    profile::Profile,
+    ActionChain, BuildHarnesses, BuildProject, GenSrcPaths, ReadConfig, RunBenchmarks,
+};
+
+// cproj bench [--iterations n] [--save-baseline name] [--baseline name] [bench names]
+
+enum BenchProjectState {
+    ValidArguments,
+    InvalidIterations,
+}
+
+pub struct BenchProject {
+    action_chain: Box<ActionChain>,
+    state: BenchProjectState,
+}
+
+impl BenchProject {
+    const DEFAULT_ITERATIONS: usize = 10;
+
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let mut state = BenchProjectState::ValidArguments;
+        let iterations = Self::get_iterations(&arg_retriever).unwrap_or_else(|| {
+            state = BenchProjectState::InvalidIterations;
+            Self::DEFAULT_ITERATIONS
+        });
+        let save_baseline = arg_retriever
+            .get_tag_args("--save-baseline")
+            .map(|args| args[0].clone());
+        let baseline = arg_retriever
+            .get_tag_args("--baseline")
+            .map(|args| args[0].clone());
+        let names = arg_retriever.get_untagged();
+
+        // Create Action Arguments
+        let profile = Profile::new(true);
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+        let src_paths = Rc::new(RefCell::new(HashSet::new()));
+        let harnesses = Rc::new(RefCell::new(vec![]));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(BuildProject::with_profile(profile.clone()))
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(GenSrcPaths::new(Rc::clone(&src_paths), Rc::clone(&config)))
+            .add(BuildHarnesses::new(
+                "benches",
+                Rc::clone(&src_paths),
+                Rc::clone(&config),
+                profile,
+                vec![],
+                names,
+                Rc::clone(&harnesses),
+            ))
+            .add(RunBenchmarks::new(
+                Rc::clone(&harnesses),
+                iterations,
+                save_baseline,
+                baseline,
+            ));
+
+        Box::new(Self {
+            action_chain,
+            state,
+        })
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        let rules = [
+            ArgRule::new("--iterations", 1),
+            ArgRule::new("--save-baseline", 1),
+            ArgRule::new("--baseline", 1),
+        ];
+        ArgRetriever::new(&rules)
+    }
+
+    fn get_iterations(arg_ret: &ArgRetriever) -> Option<usize> {
+        match arg_ret.get_tag_args("--iterations") {
+            None => Some(Self::DEFAULT_ITERATIONS),
+            Some(args) => args[0].parse().ok().filter(|val| *val > 0),
+        }
+    }
+}
+
+impl Action for BenchProject {
+    fn execute(&mut self) -> Result<()> {
+        match self.state {
+            BenchProjectState::ValidArguments => self.action_chain.execute(),
+            BenchProjectState::InvalidIterations => Err("Invalid iterations argument".into()),
+        }
+        .map_err(|err| err.prepend("Failed to benchmark project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project benchmark: "))
+    }
+}
@@ -5,11 +5,39 @@ use crate::{
     arg_retriever::{ArgRetriever, ArgRule},
     config::{Config, Lang},
     graph::Graph,
-    ActionChain, CompileFiles, FetchEditedFiles, GenDepGraph, GenSrcPaths, ReadConfig,
+    profile::Profile,
+    ActionChain, CompileFiles, EnsureDirectory, FetchEditedFiles, GenDepGraph, GenNinjaFile,
+    GenSrcPaths, ReadConfig, RunNinja,
 };
 
+// cproj build [--debug, --release] [--sanitize list] [--backend cproj, ninja]
+
+enum BuildProjectState {
+    ValidArguments,
+    InvalidProfile(String),
+    InvalidBackend,
+}
+
+/// What runs the compile and link commands
+#[derive(Clone, Copy)]
+pub enum Backend {
+    Cproj,
+    Ninja,
+}
+
+impl Backend {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cproj" => Some(Self::Cproj),
+            "ninja" => Some(Self::Ninja),
+            _ => None,
+        }
+    }
+}
+
 pub struct BuildProject {
     action_chain: Box<ActionChain>,
+    state: BuildProjectState,
 }
 
 impl BuildProject {
@@ -19,8 +47,29 @@ impl BuildProject {
         arg_retriever.load(args);
 
         // Get Necessary Arguments
-        let release_mode = Self::release_mode(&arg_retriever);
+        let backend = match arg_retriever.get_tag_args("--backend") {
+            Some(args) => Backend::from_name(&args[0]),
+            None => Some(Backend::Cproj),
+        };
+
+        match (Profile::from_args(&arg_retriever), backend) {
+            (Ok(profile), Some(backend)) => Self::with_backend(profile, backend),
+            (Err(err), _) => Box::new(Self {
+                action_chain: ActionChain::new(),
+                state: BuildProjectState::InvalidProfile(err.get_msg().to_string()),
+            }),
+            (_, None) => Box::new(Self {
+                action_chain: ActionChain::new(),
+                state: BuildProjectState::InvalidBackend,
+            }),
+        }
+    }
 
+    pub fn with_profile(profile: Profile) -> Box<Self> {
+        Self::with_backend(profile, Backend::Cproj)
+    }
+
+    pub fn with_backend(profile: Profile, backend: Backend) -> Box<Self> {
         // Create Action Arguments
         let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
         let src_paths = Rc::new(RefCell::new(HashSet::new()));
@@ -32,7 +81,28 @@ impl BuildProject {
 
         action_chain
             .add(ReadConfig::new(Rc::clone(&config)))
-            .add(GenSrcPaths::new(Rc::clone(&src_paths), Rc::clone(&config)))
+            .add(EnsureDirectory::new(profile.dir().join("log")))
+            .add(EnsureDirectory::new(profile.dir().join("obj")))
+            .add(GenSrcPaths::new(Rc::clone(&src_paths), Rc::clone(&config)));
+
+        // ninja tracks headers through depfiles and works out what changed
+        // by itself
+        if let Backend::Ninja = backend {
+            action_chain
+                .add(GenNinjaFile::new(
+                    Rc::clone(&src_paths),
+                    Rc::clone(&config),
+                    profile.clone(),
+                ))
+                .add(RunNinja::new(profile));
+
+            return Box::new(Self {
+                action_chain,
+                state: BuildProjectState::ValidArguments,
+            });
+        }
+
+        action_chain
             .add(GenDepGraph::new(
                 Rc::clone(&src_paths),
                 Rc::clone(&dependency_graph),
@@ -41,33 +111,44 @@ impl BuildProject {
                 Rc::clone(&src_paths),
                 Rc::clone(&dependency_graph),
                 Rc::clone(&changed_files),
-                release_mode,
+                profile.clone(),
             ))
             .add(CompileFiles::new(
                 Rc::clone(&src_paths),
                 Rc::clone(&changed_files),
                 Rc::clone(&config),
-                release_mode,
+                profile,
             ));
 
-        Box::new(Self { action_chain })
+        Box::new(Self {
+            action_chain,
+            state: BuildProjectState::ValidArguments,
+        })
     }
 
-    fn create_arg_retriever() -> ArgRetriever {
-        let rules = [ArgRule::new("--release", 0)];
-        ArgRetriever::new(&rules)
+    /// the argument rules of the build, for commands that build the project
+    /// before doing something with it
+    pub fn arg_rules() -> Vec<ArgRule> {
+        let mut rules = Profile::arg_rules();
+        rules.push(ArgRule::new("--backend", 1));
+        rules
     }
 
-    fn release_mode(arg_ret: &ArgRetriever) -> bool {
-        arg_ret.has_tag("--release")
+    fn create_arg_retriever() -> ArgRetriever {
+        ArgRetriever::new(&Self::arg_rules())
     }
 }
 
 impl Action for BuildProject {
     fn execute(&mut self) -> Result<()> {
-        self.action_chain
-            .execute()
-            .map_err(|err| err.prepend("Failed to build project: "))
+        match &self.state {
+            BuildProjectState::ValidArguments => self.action_chain.execute(),
+            BuildProjectState::InvalidProfile(msg) => Err(msg.as_str().into()),
+            BuildProjectState::InvalidBackend => {
+                Err("Invalid backend argument, expected cproj or ninja".into())
+            }
+        }
+        .map_err(|err| err.prepend("Failed to build project: "))
     }
 
     fn undo(&mut self) -> Result<()> {
@@ -0,0 +1,43 @@
+use crate::{
+    action::{Action, Result},
+    arg_retriever::ArgRetriever,
+    profile::Profile,
+    BuildProject,
+};
+
+/// Checks the project for compile errors without producing any objects or
+/// linking a binary
+pub struct CheckProject {
+    build_action: Box<BuildProject>,
+}
+
+impl CheckProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = ArgRetriever::new(&Profile::arg_rules());
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let build_action = match Profile::from_args(&arg_retriever) {
+            Ok(profile) => BuildProject::with_profile(profile.with_check()),
+            // lets the build action report the invalid profile
+            Err(_) => BuildProject::new(args),
+        };
+
+        Box::new(Self { build_action })
+    }
+}
+
+impl Action for CheckProject {
+    fn execute(&mut self) -> Result<()> {
+        self.build_action
+            .execute()
+            .map_err(|err| err.prepend("Failed to check project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.build_action
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project check: "))
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     action::{Action, Result},
-    ActionChain, ClearDirectory, CreateDirectory, HasFile,
+    config_file, ActionChain, ClearDirectory, CreateDirectory, HasFile,
 };
 
 pub struct CleanProject {
@@ -11,7 +11,7 @@ impl CleanProject {
     pub fn new() -> Box<Self> {
         let mut action_chain = ActionChain::new();
         action_chain
-            .add(HasFile::new("cproj.json"))
+            .add(HasFile::new(config_file::path()))
             .add(ClearDirectory::new("bin"))
             .add(CreateDirectory::new("bin"))
             .add(CreateDirectory::new("bin/debug"))
@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::ArgRetriever,
+    config::{Config, Lang},
+    config_edit::Edit,
+    ActionChain, EditConfig, GetConfigValue, ReadConfig, ShowConfig,
+};
+
+// cproj config [show, get, set, add, remove] [field] [values]
+
+enum ConfigProjectState {
+    ValidArguments,
+    InvalidSubcommand,
+    MissingField,
+    MissingValues,
+}
+
+pub struct ConfigProject {
+    action_chain: Box<ActionChain>,
+    state: ConfigProjectState,
+}
+
+impl ConfigProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = ArgRetriever::new(&[]);
+        arg_retriever.load(args);
+
+        // Create Action Arguments
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+        let origins = Rc::new(RefCell::new(vec![]));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        let args = arg_retriever.get_untagged();
+        let subcommand = args.first().map(|val| val.as_str()).unwrap_or("");
+        let field = args.get(1);
+        let values = args.iter().skip(2).cloned().collect::<Vec<_>>();
+
+        let state = match (subcommand, field) {
+            ("show", _) => {
+                action_chain
+                    .add(ReadConfig::with_origins(
+                        Rc::clone(&config),
+                        Rc::clone(&origins),
+                    ))
+                    .add(ShowConfig::new(config, origins));
+                ConfigProjectState::ValidArguments
+            }
+            ("get" | "set" | "add" | "remove", None) => ConfigProjectState::MissingField,
+            ("get", Some(field)) => {
+                action_chain.add(GetConfigValue::new(field));
+                ConfigProjectState::ValidArguments
+            }
+            // only remove can go without values, which removes the field
+            ("set" | "add", Some(_)) if values.is_empty() => ConfigProjectState::MissingValues,
+            (name, Some(field)) => match Edit::from_name(name, values) {
+                Some(edit) => {
+                    action_chain.add(EditConfig::new(field, edit));
+                    ConfigProjectState::ValidArguments
+                }
+                None => ConfigProjectState::InvalidSubcommand,
+            },
+            _ => ConfigProjectState::InvalidSubcommand,
+        };
+
+        Box::new(Self {
+            action_chain,
+            state,
+        })
+    }
+}
+
+impl Action for ConfigProject {
+    fn execute(&mut self) -> Result<()> {
+        match self.state {
+            ConfigProjectState::ValidArguments => self.action_chain.execute(),
+            ConfigProjectState::InvalidSubcommand => {
+                Err("Invalid subcommand, expected show, get, set, add or remove".into())
+            }
+            ConfigProjectState::MissingField => Err("No field was given".into()),
+            ConfigProjectState::MissingValues => Err("No values were given".into()),
+        }
+        .map_err(|err| err.prepend("Failed to run config command: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo config command: "))
+    }
+}
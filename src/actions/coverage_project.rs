@@ -0,0 +1,153 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Kind, Lang},
+    crash::CrashReport,
+    profile::Profile,
+    ActionChain, BuildProject, ClearCoverageData, CollectCoverage, GenSrcPaths, ReadConfig,
+    RunBinary, RunTestCases,
+};
+
+// cproj coverage [--release] [--sanitize list] [--fail-under percent]
+
+enum CoverageProjectState {
+    ValidArguments,
+    InvalidProfile(String),
+    InvalidThreshold,
+}
+
+pub struct CoverageProject {
+    action_chain: Box<ActionChain>,
+    run_action: Box<dyn Action>,
+    collect_action: Box<dyn Action>,
+    config: Rc<RefCell<Config>>,
+    state: CoverageProjectState,
+}
+
+impl CoverageProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let mut state = CoverageProjectState::ValidArguments;
+        let profile = Profile::from_args(&arg_retriever)
+            .unwrap_or_else(|err| {
+                state = CoverageProjectState::InvalidProfile(err.get_msg().to_string());
+                Profile::new(false)
+            })
+            .with_coverage();
+        let fail_under = Self::get_fail_under(&arg_retriever).unwrap_or_else(|| {
+            state = CoverageProjectState::InvalidThreshold;
+            None
+        });
+
+        // Create Action Arguments
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+        let src_paths = Rc::new(RefCell::new(HashSet::new()));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(BuildProject::with_profile(profile.clone()))
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(GenSrcPaths::new(Rc::clone(&src_paths), Rc::clone(&config)))
+            .add(ClearCoverageData::new(
+                Rc::clone(&src_paths),
+                profile.clone(),
+            ));
+
+        // the tests exercise the project better than a plain run does
+        let run_action: Box<dyn Action> = if RunTestCases::cases_dir().is_dir() {
+            RunTestCases::new(
+                Rc::clone(&config),
+                profile.clone(),
+                false,
+                vec![],
+                CrashReport::default(),
+                None,
+            )
+        } else {
+            RunBinary::new(
+                Rc::clone(&config),
+                profile.clone(),
+                [arg_retriever.get_untagged(), arg_retriever.get_trailing()].concat(),
+                CrashReport::default(),
+                None,
+            )
+        };
+
+        let collect_action = CollectCoverage::new(
+            Rc::clone(&src_paths),
+            Rc::clone(&config),
+            profile,
+            fail_under,
+        );
+
+        Box::new(Self {
+            action_chain,
+            run_action,
+            collect_action,
+            config,
+            state,
+        })
+    }
+
+    // coverage is collected even when the tests fail, since that is when it
+    // helps the most, but the failure is still what gets returned
+    fn collect(&mut self) -> Result<()> {
+        self.action_chain.execute()?;
+
+        if self.config.borrow().kind != Kind::Bin && !RunTestCases::cases_dir().is_dir() {
+            return Err(format!(
+                "libraries have no binary to run, add test cases to {} to cover them",
+                RunTestCases::cases_dir().to_str().unwrap()
+            )
+            .into());
+        }
+
+        let run_result = self.run_action.execute();
+        let collect_result = self.collect_action.execute();
+
+        run_result.and(collect_result)
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        let mut rules = Profile::arg_rules();
+        rules.push(ArgRule::new("--fail-under", 1));
+        ArgRetriever::new(&rules)
+    }
+
+    fn get_fail_under(arg_ret: &ArgRetriever) -> Option<Option<f64>> {
+        match arg_ret.get_tag_args("--fail-under") {
+            None => Some(None),
+            Some(args) => args[0]
+                .trim_end_matches('%')
+                .parse()
+                .ok()
+                .filter(|val| (0.0..=100.0).contains(val))
+                .map(Some),
+        }
+    }
+}
+
+impl Action for CoverageProject {
+    fn execute(&mut self) -> Result<()> {
+        match &self.state {
+            CoverageProjectState::ValidArguments => self.collect(),
+            CoverageProjectState::InvalidProfile(msg) => Err(msg.as_str().into()),
+            CoverageProjectState::InvalidThreshold => Err("Invalid fail-under argument".into()),
+        }
+        .map_err(|err| err.prepend("Failed to collect project coverage: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project coverage: "))
+    }
+}
@@ -0,0 +1,99 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    profile::Profile,
+    ActionChain, BuildProject, LaunchDebugger, ReadConfig,
+};
+
+// cproj debug [--break file:line[,file:line...]] [--debugger name] [-- args]
+
+enum DebugProjectState {
+    ValidArguments,
+    InvalidBreakpoint(String),
+}
+
+pub struct DebugProject {
+    action_chain: Box<ActionChain>,
+    state: DebugProjectState,
+}
+
+impl DebugProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let mut state = DebugProjectState::ValidArguments;
+        let breakpoints: Vec<_> = arg_retriever
+            .get_tag_args("--break")
+            .map(|args| args[0].split(',').map(|val| val.to_string()).collect())
+            .unwrap_or_default();
+        if let Some(breakpoint) = breakpoints
+            .iter()
+            .find(|val| !Self::is_valid_breakpoint(val))
+        {
+            state = DebugProjectState::InvalidBreakpoint(breakpoint.clone());
+        }
+        let debugger = arg_retriever
+            .get_tag_args("--debugger")
+            .map(|args| args[0].clone());
+        let bin_args = [arg_retriever.get_untagged(), arg_retriever.get_trailing()].concat();
+
+        // Create Action Arguments
+        let profile = Profile::new(false);
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(BuildProject::with_profile(profile.clone()))
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(LaunchDebugger::new(
+                Rc::clone(&config),
+                profile,
+                debugger,
+                breakpoints,
+                bin_args,
+            ));
+
+        Box::new(Self {
+            action_chain,
+            state,
+        })
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        ArgRetriever::new(&[ArgRule::new("--break", 1), ArgRule::new("--debugger", 1)])
+    }
+
+    // file:line
+    fn is_valid_breakpoint(breakpoint: &str) -> bool {
+        match breakpoint.rsplit_once(':') {
+            Some((file, line)) => !file.is_empty() && line.parse::<u32>().is_ok(),
+            None => false,
+        }
+    }
+}
+
+impl Action for DebugProject {
+    fn execute(&mut self) -> Result<()> {
+        match &self.state {
+            DebugProjectState::ValidArguments => self.action_chain.execute(),
+            DebugProjectState::InvalidBreakpoint(breakpoint) => {
+                Err(format!("Invalid breakpoint {}, expected file:line", breakpoint).into())
+            }
+        }
+        .map_err(|err| err.prepend("Failed to debug project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project debug: "))
+    }
+}
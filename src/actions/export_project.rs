@@ -0,0 +1,76 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    export::Format,
+    ActionChain, ExportBuildFile, GenSrcPaths, ReadConfig,
+};
+
+// cproj export [cmake, make] [--force]
+
+enum ExportProjectState {
+    ValidArguments,
+    InvalidFormat,
+}
+
+pub struct ExportProject {
+    action_chain: Box<ActionChain>,
+    state: ExportProjectState,
+}
+
+impl ExportProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = ArgRetriever::new(&[ArgRule::new("--force", 0)]);
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let mut state = ExportProjectState::ValidArguments;
+        let format = arg_retriever
+            .get_untagged()
+            .first()
+            .and_then(|name| Format::from_name(name))
+            .unwrap_or_else(|| {
+                state = ExportProjectState::InvalidFormat;
+                Format::CMake
+            });
+        let force = arg_retriever.has_tag("--force");
+
+        // Create Action Arguments
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+        let src_paths = Rc::new(RefCell::new(HashSet::new()));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(GenSrcPaths::new(Rc::clone(&src_paths), Rc::clone(&config)))
+            .add(ExportBuildFile::new(format, src_paths, config, force));
+
+        Box::new(Self {
+            action_chain,
+            state,
+        })
+    }
+}
+
+impl Action for ExportProject {
+    fn execute(&mut self) -> Result<()> {
+        match self.state {
+            ExportProjectState::ValidArguments => self.action_chain.execute(),
+            ExportProjectState::InvalidFormat => {
+                Err("Invalid format argument, expected cmake or make".into())
+            }
+        }
+        .map_err(|err| err.prepend("Failed to export project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project export: "))
+    }
+}
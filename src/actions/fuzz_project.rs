@@ -0,0 +1,134 @@
+use std::{cell::RefCell, collections::HashSet, path, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    profile::Profile,
+    ActionChain, BuildHarnesses, BuildProject, EnsureDirectory, GenSrcPaths, ReadConfig, RunFuzzer,
+};
+
+// cproj fuzz [--target] target [--runs n] [--max-total-time seconds]
+
+enum FuzzProjectState {
+    ValidArguments,
+    TargetNotFound,
+    InvalidRuns,
+    InvalidMaxTotalTime,
+}
+
+pub struct FuzzProject {
+    action_chain: Box<ActionChain>,
+    state: FuzzProjectState,
+}
+
+impl FuzzProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let mut state = FuzzProjectState::ValidArguments;
+        let target = Self::get_target(&arg_retriever).unwrap_or_else(|| {
+            state = FuzzProjectState::TargetNotFound;
+            String::new()
+        });
+
+        let mut fuzzer_args = vec![];
+
+        match Self::get_number(&arg_retriever, "--runs") {
+            Some(Some(runs)) => fuzzer_args.push(format!("-runs={}", runs)),
+            Some(None) => {}
+            None => state = FuzzProjectState::InvalidRuns,
+        }
+
+        match Self::get_number(&arg_retriever, "--max-total-time") {
+            Some(Some(time)) => fuzzer_args.push(format!("-max_total_time={}", time)),
+            Some(None) => {}
+            None => state = FuzzProjectState::InvalidMaxTotalTime,
+        }
+
+        // Create Action Arguments
+        let profile = Profile::new(false).with_fuzzing();
+        let fuzz_root = path::PathBuf::from("fuzz");
+        let corpus_dir = fuzz_root.join("corpus").join(&target);
+        let artifact_dir = fuzz_root.join("artifacts").join(&target);
+
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+        let src_paths = Rc::new(RefCell::new(HashSet::new()));
+        let harnesses = Rc::new(RefCell::new(vec![]));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(BuildProject::with_profile(profile.clone()))
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(GenSrcPaths::new(Rc::clone(&src_paths), Rc::clone(&config)))
+            .add(BuildHarnesses::new(
+                &fuzz_root,
+                Rc::clone(&src_paths),
+                Rc::clone(&config),
+                profile,
+                vec!["-fsanitize=fuzzer".to_string()],
+                vec![target],
+                Rc::clone(&harnesses),
+            ))
+            .add(EnsureDirectory::new(&corpus_dir))
+            .add(EnsureDirectory::new(&artifact_dir))
+            .add(RunFuzzer::new(
+                Rc::clone(&harnesses),
+                corpus_dir,
+                artifact_dir,
+                fuzzer_args,
+            ));
+
+        Box::new(Self {
+            action_chain,
+            state,
+        })
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        let rules = [
+            ArgRule::new("--target", 1),
+            ArgRule::new("--runs", 1),
+            ArgRule::new("--max-total-time", 1),
+        ];
+        ArgRetriever::new(&rules)
+    }
+
+    fn get_target(arg_ret: &ArgRetriever) -> Option<String> {
+        arg_ret
+            .get_tag_args("--target")
+            .map(|args| args[0].clone())
+            .or_else(|| arg_ret.get_untagged().first().cloned())
+    }
+
+    /// None if the argument isnt a number, Some(None) if it wasnt passed
+    fn get_number(arg_ret: &ArgRetriever, tag: &str) -> Option<Option<u64>> {
+        match arg_ret.get_tag_args(tag) {
+            None => Some(None),
+            Some(args) => args[0].parse().ok().map(Some),
+        }
+    }
+}
+
+impl Action for FuzzProject {
+    fn execute(&mut self) -> Result<()> {
+        match self.state {
+            FuzzProjectState::ValidArguments => self.action_chain.execute(),
+            FuzzProjectState::TargetNotFound => Err("Fuzz target not passed".into()),
+            FuzzProjectState::InvalidRuns => Err("Invalid runs argument".into()),
+            FuzzProjectState::InvalidMaxTotalTime => Err("Invalid max-total-time argument".into()),
+        }
+        .map_err(|err| err.prepend("Failed to fuzz project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project fuzzing: "))
+    }
+}
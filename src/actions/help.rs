@@ -34,15 +34,111 @@ impl Action for Help {
         println!("\t--name: The name of the project, '--name' can optionally be ommitted");
         println!("\t--lang: The language for the project [c, cpp, c++] (ignores casing), defaults");
         println!("\t        to '--lang c'");
+        println!("\t--template: The files the project starts with [bin, lib, lib+tests,");
+        println!("\t            cli-with-args] or a folder in ~/.config/cproj/templates, where");
+        println!("\t            {{{{name}}}} and {{{{lang}}}} are filled in, defaults to '--template bin'");
+        println!("\t--vcs: The version control to set up [git, none], defaults to '--vcs git'");
+        println!("\t       which runs git init and writes a .gitignore for the build outputs");
+        println!("\t--format: The format of the config [json, toml], defaults to '--format json'");
         println!("\ncproj init - initializes the current folder with a c/c++ project");
         println!("\t--name: The name of the project, can optionally be ommited for cproj to use");
         println!("\t        the name of the current folder");
         println!("\t--lang: The language for the project [c, cpp, c++] (ignores casing), defaults");
-        println!("\t        to '--lang c'");
+        println!("\t        to the language of the files in src, or c if there are none");
+        println!("\tExisting files are kept and only the missing directories are created, a");
+        println!("\tsrc/main.c is only added if src has no code");
+        println!("\tCode outside of src is reported and has to be moved into src first");
+        println!("\t--vcs: The version control to set up [git, none], see cproj new");
+        println!("\t--format: The format of the config [json, toml], see cproj new");
         println!("\ncproj run - builds and runs the project");
         println!("\t--release: runs the project in release mode instead of debug mode");
+        println!("\t--sanitize: builds the project with the given sanitizers, see cproj build");
+        println!("\t--backend: what runs the build, see cproj build");
+        println!("\tAny arguments after '--' are passed to the program, cproj exits with the");
+        println!("\tprogram's exit code");
+        println!("\t--runner: a command to run the program through, such as \"valgrind -q\",");
+        println!("\t          overrides the runner of the profile in cproj.json");
+        println!("\t--backtrace: if the program crashes, reruns it under gdb to print a backtrace");
+        println!("\t--core-dump: enables core dumps in debug builds and prints the backtrace of");
+        println!("\t             the core dump if the program crashes");
         println!("\ncproj build - builds the project");
         println!("\t--release: builds the project in release mode instead of debug mode");
+        println!("\t--sanitize: builds the project with a comma separated list of sanitizers");
+        println!("\t            [address, undefined, thread, leak], sanitized builds are kept");
+        println!("\t            in their own directory such as bin/debug-address-undefined");
+        println!("\t--backend: what runs the build [cproj, ninja], defaults to cproj. ninja");
+        println!("\t           builds from bin/[mode]/build.ninja, which cproj generates");
+        println!("\ncproj test - builds the project and runs it against the cases in tests/cases");
+        println!("\tEach case is a [name].in file that is passed to the binary's stdin, its");
+        println!("\tstdout is compared against [name].out. [name].args and [name].exitcode can");
+        println!("\toptionally hold the arguments and the expected exit code of the case");
+        println!("\t--release: tests the project in release mode instead of debug mode");
+        println!("\t--sanitize: builds the project with the given sanitizers, see cproj build");
+        println!("\t--backend: what runs the build, see cproj build");
+        println!("\t--bless: rewrites the expected output of each case from its current output");
+        println!("\t--backtrace, --core-dump: reports on crashing cases, see cproj run");
+        println!("\t--runner: a command to run the binary through, see cproj run");
+        println!("\tAny other arguments are the names of the cases to run, defaults to all");
+        println!("\ncproj bench - builds the programs in benches in release mode and times them");
+        println!("\tEach file in benches is linked against every object of the project except");
+        println!("\tthe one that defines main");
+        println!("\t--iterations: how many times each benchmark is run, defaults to 10");
+        println!("\t--save-baseline: saves the results under the given name in bin/baselines");
+        println!("\t--baseline: compares the results against the baseline with the given name");
+        println!("\tAny other arguments are the names of the benchmarks to run, defaults to all");
+        println!("\ncproj coverage - builds the project with --coverage and reports its coverage");
+        println!("\tRuns cproj test, or the binary if there are no tests, then prints the line");
+        println!("\tand branch coverage of each file and writes lcov.info and an html report to");
+        println!("\tbin/debug-coverage/coverage");
+        println!("\t--release: collects coverage in release mode instead of debug mode");
+        println!("\t--sanitize: builds the project with the given sanitizers, see cproj build");
+        println!("\t--fail-under: fails if the line coverage is under the given percentage");
+        println!("\ncproj fuzz - builds a libFuzzer target in fuzz with clang and runs it");
+        println!("\t--target: The name of the target, '--target' can optionally be ommitted");
+        println!("\t--runs: stops the fuzzer after the given number of runs");
+        println!("\t--max-total-time: stops the fuzzer after the given number of seconds");
+        println!("\tThe corpus is kept in fuzz/corpus/[target] and crashing inputs are saved");
+        println!("\tto fuzz/artifacts/[target]");
+        println!("\ncproj check - checks the project for compile errors without building it");
+        println!("\t--release: checks the project in release mode instead of debug mode");
+        println!("\t--sanitize: checks the project with the given sanitizers, see cproj build");
+        println!("\ncproj watch - rebuilds the project whenever src, include or cproj.json change");
+        println!("\tThe first argument is what to run on every change [build, run, test, check],");
+        println!("\tdefaults to build. Any other arguments are passed along to it");
+        println!("\ncproj debug - builds the project in debug mode and starts gdb or lldb on it");
+        println!("\t--break: sets breakpoints, given as a comma separated list of file:line");
+        println!("\t--debugger: the debugger to use, overrides the debugger field of cproj.json");
+        println!("\tAny arguments after '--' are passed to the program");
+        println!("\ncproj install - builds the project and installs it into a prefix");
+        println!("\tBinaries go to bin, libraries to lib and for libraries the headers in");
+        println!("\tinclude go to include/[name]. Set DESTDIR to stage the install elsewhere");
+        println!("\t--prefix: where to install the project, defaults to /usr/local");
+        println!("\t--release: installs the release build instead of the debug build");
+        println!("\ncproj uninstall - removes every file listed in install_manifest.txt");
+        println!("\ncproj package - builds the project in release mode and creates");
+        println!("\tdist/[name]-[version]-[arch].tar.gz along with its sha256 checksum");
+        println!("\t--source: packages src, include and cproj.json instead of the build output");
+        println!("\ncproj export - writes a CMakeLists.txt or a Makefile for the project");
+        println!("\tThe first argument is the build system to export to [cmake, make]");
+        println!("\t--force: replaces the file even if it wasnt generated by cproj");
+        println!("\ncproj import - writes a cproj.json from compile_commands.json or a Makefile");
+        println!("\tThe first argument is the file to import, defaults to compile_commands.json");
+        println!("\tor the Makefile. Flags that could not be mapped are reported");
+        println!("\t--name: The name of the project, defaults to the name of the current folder");
+        println!(
+            "\ncproj migrate - rewrites cproj.json or cproj.toml in the current config layout"
+        );
+        println!(
+            "\ncproj config show - prints the effective config and where each value came from,"
+        );
+        println!("\twhich layers ~/.config/cproj/config.json, cproj.json and the CC, CXX, CFLAGS,");
+        println!("\tCXXFLAGS and LDFLAGS variables");
+        println!("\ncproj config get [field] - prints a field of cproj.json, such as profiles.debug.flags");
+        println!(
+            "\ncproj config [set, add, remove] [field] [values] - edits a field of cproj.json"
+        );
+        println!("\tset replaces the value, add and remove change the items of a list and remove");
+        println!("\twithout values removes the field. The config is checked before it is written");
         println!("\ncproj clean - clears the bin directory");
         println!("\ncproj help - prints this dialogue");
 
@@ -0,0 +1,81 @@
+use std::env;
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    import::Source,
+    ActionChain, ImportConfig,
+};
+
+// cproj import [--name name] [compile_commands.json, makefile]
+
+enum ImportProjectState {
+    ValidArguments,
+    NoBuildFound,
+}
+
+pub struct ImportProject {
+    action_chain: Box<ActionChain>,
+    state: ImportProjectState,
+}
+
+impl ImportProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = ArgRetriever::new(&[ArgRule::new("--name", 1)]);
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let name = arg_retriever
+            .get_tag_args("--name")
+            .map(|args| args[0].clone())
+            .unwrap_or_else(|| {
+                env::current_dir()
+                    .unwrap()
+                    .file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            });
+
+        let source = match arg_retriever.get_untagged().first() {
+            Some(path) => Some(Source::from_path(path)),
+            None => Source::detect(),
+        };
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        let state = match source {
+            Some(source) => {
+                action_chain.add(ImportConfig::new(source, &name));
+                ImportProjectState::ValidArguments
+            }
+            None => ImportProjectState::NoBuildFound,
+        };
+
+        Box::new(Self {
+            action_chain,
+            state,
+        })
+    }
+}
+
+impl Action for ImportProject {
+    fn execute(&mut self) -> Result<()> {
+        match self.state {
+            ImportProjectState::ValidArguments => self.action_chain.execute(),
+            ImportProjectState::NoBuildFound => {
+                Err("No compile_commands.json or Makefile was found".into())
+            }
+        }
+        .map_err(|err| err.prepend("Failed to import project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project import: "))
+    }
+}
@@ -1,21 +1,33 @@
-use std::{env, path};
+use std::{env, fs, path};
 
 use crate::action::{Action, Result};
-use crate::actions::{ActionChain, CreateDirectory};
+use crate::actions::ActionChain;
 use crate::arg_retriever::{ArgRetriever, ArgRule};
-use crate::config::{Config, Lang};
-use crate::CreateFile;
+use crate::config::{Config, Kind, Lang};
+use crate::config_file;
+use crate::vcs::{self, Vcs};
+use crate::{
+    entry_point, CompileFiles, CreateFile, EnsureDirectory, GenSrcPaths, GitInit, NewProject,
+};
 
-/// cproj new --name [name] --lang [c, cpp] (default = --lang c)
+// cproj init --name [name] --lang [c, cpp] --vcs [git, none] --format [json, toml]
+// (default = --lang detected from src --vcs git --format json)
 
 enum InitProjectState {
     ValidArguments,
     InvalidLang,
+    InvalidVcs,
+    InvalidFormat,
+    AlreadyInitialized,
+    CodeOutsideSrc(Vec<String>),
 }
 
 pub struct InitProject {
     action_chain: Box<ActionChain>,
     state: InitProjectState,
+    // what was found out about the existing sources, printed before anything
+    // gets created
+    notes: Vec<String>,
 }
 
 impl InitProject {
@@ -26,46 +38,121 @@ impl InitProject {
 
         // Get Necessary Arguments
         let mut state = InitProjectState::ValidArguments;
+        let mut notes = vec![];
+        let project_root = path::PathBuf::from(".");
+        let src_files = Self::get_src_files(&project_root);
+
         let name = Self::get_project_name(&arg_retriever);
-        let lang = Self::get_project_lang(&arg_retriever).unwrap_or_else(|| {
-            state = InitProjectState::InvalidLang;
-            Lang::C
+        let lang =
+            Self::get_project_lang(&arg_retriever, &src_files, &mut notes).unwrap_or_else(|| {
+                state = InitProjectState::InvalidLang;
+                Lang::C
+            });
+
+        let (vcs, vcs_explicit) = Vcs::from_args(&arg_retriever).unwrap_or_else(|| {
+            state = InitProjectState::InvalidVcs;
+            (Vcs::None, false)
+        });
+
+        let format = NewProject::get_config_format(&arg_retriever).unwrap_or_else(|| {
+            state = InitProjectState::InvalidFormat;
+            config_file::Format::Json
         });
 
+        if config_file::exists(&project_root) {
+            state = InitProjectState::AlreadyInitialized;
+        }
+
+        // cproj only builds src, so code anywhere else would silently be left
+        // out of the build
+        let outside_src: Vec<_> = src_files
+            .iter()
+            .filter(|path| CompileFiles::is_code_file(path))
+            .filter(|path| !path.starts_with(project_root.join("src")))
+            .map(|path| path.strip_prefix(&project_root).unwrap_or(path))
+            .map(|path| path.to_str().unwrap().to_string())
+            .collect();
+        if !outside_src.is_empty() {
+            state = InitProjectState::CodeOutsideSrc(outside_src);
+        }
+
         // Create Action Arguments
-        let project_root = path::PathBuf::from(".");
+        let code_files: Vec<_> = src_files
+            .iter()
+            .filter(|path| CompileFiles::is_code_file(path))
+            .cloned()
+            .collect();
 
-        let config_string = serde_json::to_string_pretty(&Config::new(&name, lang)).unwrap();
-        let config_path = project_root.join("cproj.json");
-        let config_contents = config_string.as_bytes();
+        let mut config = Config::new(&name, lang);
+
+        // existing code without a main is most likely a library, so a main
+        // is only added to a tree without any code
+        let entry_point = if code_files.is_empty() {
+            Some(match lang {
+                Lang::C => project_root.join("src").join("main.c"),
+                Lang::Cpp => project_root.join("src").join("main.cpp"),
+            })
+        } else {
+            match entry_point::find_main(&code_files) {
+                Some(path) => notes.push(format!("found main in {}", path.to_str().unwrap())),
+                None => {
+                    notes.push("no main function was found, building a static library".into());
+                    config.kind = Kind::StaticLib;
+                }
+            }
 
-        let entry_point = match lang {
-            Lang::C => project_root.join("src").join("main.c"),
-            Lang::Cpp => project_root.join("src").join("main.cpp"),
+            None
         };
         let entry_contents = b"int main(int argc, char *argv[])\n{\n\treturn 0;\n}\n";
 
+        let config_string = format.serialize(&config);
+        let config_path = project_root.join(format.file_name());
+        let config_contents = config_string.as_bytes();
+
         // Create Action Chain
         let mut action_chain = ActionChain::new();
 
         action_chain
-            .add(CreateDirectory::new(project_root.join("bin")))
-            .add(CreateDirectory::new(project_root.join("include")))
-            .add(CreateDirectory::new(project_root.join("lib")))
-            .add(CreateDirectory::new(project_root.join("src")))
-            .add(CreateDirectory::new(project_root.join("bin/log")))
-            .add(CreateDirectory::new(project_root.join("bin/obj")))
-            .add(CreateFile::new(entry_point, entry_contents))
-            .add(CreateFile::new(config_path, config_contents));
+            .add(EnsureDirectory::new(project_root.join("include")))
+            .add(EnsureDirectory::new(project_root.join("lib")))
+            .add(EnsureDirectory::new(project_root.join("src")))
+            .add(EnsureDirectory::new(project_root.join("bin/debug/log")))
+            .add(EnsureDirectory::new(project_root.join("bin/debug/obj")))
+            .add(EnsureDirectory::new(project_root.join("bin/release/log")))
+            .add(EnsureDirectory::new(project_root.join("bin/release/obj")));
+
+        // an existing .gitignore is left as it is
+        if vcs == Vcs::Git {
+            action_chain.add(GitInit::new(&project_root, vcs_explicit));
+
+            if !project_root.join(".gitignore").exists() {
+                action_chain.add(CreateFile::new(
+                    project_root.join(".gitignore"),
+                    vcs::GITIGNORE.as_bytes(),
+                ));
+            }
+        }
+
+        if let Some(entry_point) = entry_point {
+            action_chain.add(CreateFile::new(entry_point, entry_contents));
+        }
+
+        action_chain.add(CreateFile::new(config_path, config_contents));
 
         Box::new(Self {
             action_chain,
             state,
+            notes,
         })
     }
 
     fn create_arg_retriever() -> ArgRetriever {
-        let rules = [ArgRule::new("--name", 1), ArgRule::new("--lang", 1)];
+        let mut rules = vec![
+            ArgRule::new("--name", 1),
+            ArgRule::new("--lang", 1),
+            ArgRule::new("--format", 1),
+        ];
+        rules.extend(Vcs::arg_rules());
         ArgRetriever::new(&rules)
     }
 
@@ -85,23 +172,98 @@ impl InitProject {
             })
     }
 
-    fn get_project_lang(arg_ret: &ArgRetriever) -> Option<Lang> {
+    // a lang given on the command line wins, otherwise any c++ file makes it
+    // a c++ project
+    fn get_project_lang(
+        arg_ret: &ArgRetriever,
+        src_files: &[path::PathBuf],
+        notes: &mut Vec<String>,
+    ) -> Option<Lang> {
         match arg_ret.get_tag_args("--lang") {
-            None => Some(Lang::C),
-            Some(args) => match args[0].to_lowercase().as_str() {
-                "c" => Some(Lang::C),
-                "cpp" | "c++" => Some(Lang::Cpp),
-                _ => None,
-            },
+            None => {
+                let c_exts = GenSrcPaths::get_extensions(Lang::C);
+                let is_cpp = src_files.iter().any(|path| {
+                    path.extension()
+                        .is_some_and(|ext| !c_exts.contains(&ext.to_string_lossy().to_string()))
+                });
+
+                if is_cpp {
+                    notes.push("found c++ sources, using --lang cpp".into());
+                    Some(Lang::Cpp)
+                } else {
+                    Some(Lang::C)
+                }
+            }
+            Some(args) => Lang::from_name(&args[0]),
         }
     }
+
+    // the c and c++ files already in the project, leaving out build output,
+    // hidden folders and the folders cproj builds on their own
+    fn get_src_files(project_root: &path::Path) -> Vec<path::PathBuf> {
+        const SKIPPED: [&str; 5] = ["bin", "build", "dist", "benches", "fuzz"];
+
+        let exts = GenSrcPaths::get_extensions(Lang::Cpp);
+        let mut src_files = vec![];
+        let mut directories = vec![project_root.to_path_buf()];
+
+        while let Some(dir) = directories.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_skipped = name.starts_with('.')
+                    || (dir == project_root && SKIPPED.contains(&name.as_str()));
+
+                if path.is_dir() {
+                    if !is_skipped {
+                        directories.push(path);
+                    }
+                } else if path
+                    .extension()
+                    .is_some_and(|ext| exts.contains(&ext.to_string_lossy().to_string()))
+                {
+                    src_files.push(path);
+                }
+            }
+        }
+
+        src_files.sort();
+        src_files
+    }
 }
 
 impl Action for InitProject {
     fn execute(&mut self) -> Result<()> {
-        match self.state {
-            InitProjectState::ValidArguments => self.action_chain.execute(),
+        match &self.state {
+            InitProjectState::ValidArguments => {
+                if !self.notes.is_empty() {
+                    println!("=> Adopting Existing Sources...");
+                    for note in &self.notes {
+                        println!("    -> {}", note);
+                    }
+                }
+
+                self.action_chain.execute()
+            }
             InitProjectState::InvalidLang => Err("Invalid lang argument".into()),
+            InitProjectState::InvalidVcs => {
+                Err("Invalid vcs argument, expected git or none".into())
+            }
+            InitProjectState::InvalidFormat => {
+                Err("Invalid format argument, expected json or toml".into())
+            }
+            InitProjectState::AlreadyInitialized => {
+                Err("The folder already has a cproj.json or cproj.toml".into())
+            }
+            InitProjectState::CodeOutsideSrc(paths) => Err(format!(
+                "Found code outside of src, which cproj doesnt build: {}. Move it into src and run cproj init again",
+                paths.join(", ")
+            )
+            .into()),
         }
         .map_err(|err| err.prepend("Failed to initialize project: "))
     }
@@ -0,0 +1,67 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    profile::Profile,
+    ActionChain, BuildProject, InstallFiles, ReadConfig,
+};
+
+// cproj install [--prefix path] [--release] (default = --prefix /usr/local)
+
+pub struct InstallProject {
+    action_chain: Box<ActionChain>,
+}
+
+impl InstallProject {
+    const DEFAULT_PREFIX: &'static str = "/usr/local";
+
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        // an invalid profile is reported by the build action before it gets used
+        let profile = Profile::from_args(&arg_retriever).unwrap_or_else(|_| Profile::new(false));
+        let prefix = arg_retriever
+            .get_tag_args("--prefix")
+            .map_or(PathBuf::from(Self::DEFAULT_PREFIX), |args| {
+                PathBuf::from(&args[0])
+            });
+
+        // Create Action Arguments
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(BuildProject::new(args))
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(InstallFiles::new(config, profile, prefix));
+
+        Box::new(Self { action_chain })
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        let mut rules = Profile::arg_rules();
+        rules.push(ArgRule::new("--prefix", 1));
+        ArgRetriever::new(&rules)
+    }
+}
+
+impl Action for InstallProject {
+    fn execute(&mut self) -> Result<()> {
+        self.action_chain
+            .execute()
+            .map_err(|err| err.prepend("Failed to install project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project install: "))
+    }
+}
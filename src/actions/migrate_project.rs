@@ -0,0 +1,33 @@
+use crate::{
+    action::{Action, Result},
+    ActionChain, MigrateConfig,
+};
+
+// cproj migrate
+
+pub struct MigrateProject {
+    action_chain: Box<ActionChain>,
+}
+
+impl MigrateProject {
+    pub fn new() -> Box<Self> {
+        let mut action_chain = ActionChain::new();
+        action_chain.add(MigrateConfig::new());
+
+        Box::new(Self { action_chain })
+    }
+}
+
+impl Action for MigrateProject {
+    fn execute(&mut self) -> Result<()> {
+        self.action_chain
+            .execute()
+            .map_err(|err| err.prepend("Failed to migrate project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project migration: "))
+    }
+}
@@ -1,18 +1,46 @@
 mod action_chain;
 mod sub_actions;
 
+mod bench_project;
 mod build_project;
+mod check_project;
 mod clean_project;
+mod config_project;
+mod coverage_project;
+mod debug_project;
+mod export_project;
+mod fuzz_project;
 mod help;
+mod import_project;
 mod init_project;
+mod install_project;
+mod migrate_project;
 mod new_project;
+mod package_project;
 mod run_project;
+mod test_project;
+mod uninstall_project;
+mod watch_project;
 
 pub use action_chain::*;
+pub use bench_project::*;
 pub use build_project::*;
+pub use check_project::*;
 pub use clean_project::*;
+pub use config_project::*;
+pub use coverage_project::*;
+pub use debug_project::*;
+pub use export_project::*;
+pub use fuzz_project::*;
 pub use help::*;
+pub use import_project::*;
 pub use init_project::*;
+pub use install_project::*;
+pub use migrate_project::*;
 pub use new_project::*;
+pub use package_project::*;
 pub use run_project::*;
 pub use sub_actions::*;
+pub use test_project::*;
+pub use uninstall_project::*;
+pub use watch_project::*;
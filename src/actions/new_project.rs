@@ -4,14 +4,21 @@ use crate::action::{Action, Result};
 use crate::actions::{ActionChain, CreateDirectory};
 use crate::arg_retriever::{ArgRetriever, ArgRule};
 use crate::config::{Config, Lang};
-use crate::CreateFile;
+use crate::config_file;
+use crate::template::Template;
+use crate::vcs::{self, Vcs};
+use crate::{CreateFile, EnsureDirectory, GitInit};
 
-/// cproj new --name [name] --lang [c, cpp] (default = --lang c)
+// cproj new --name [name] --lang [c, cpp] --template [name] --vcs [git, none]
+// --format [json, toml] (default = --lang c --template bin --vcs git --format json)
 
 enum NewProjectState {
     ValidArguments,
     NameNotFound,
     InvalidLang,
+    InvalidTemplate(String),
+    InvalidVcs,
+    InvalidFormat,
 }
 
 pub struct NewProject {
@@ -36,19 +43,33 @@ impl NewProject {
             Lang::C
         });
 
+        let (vcs, vcs_explicit) = Vcs::from_args(&arg_retriever).unwrap_or_else(|| {
+            state = NewProjectState::InvalidVcs;
+            (Vcs::None, false)
+        });
+        let format = Self::get_config_format(&arg_retriever).unwrap_or_else(|| {
+            state = NewProjectState::InvalidFormat;
+            config_file::Format::Json
+        });
+        let template_name = arg_retriever
+            .get_tag_args("--template")
+            .map(|args| args[0].clone())
+            .unwrap_or_else(|| "bin".to_string());
+        let template = Template::load(&template_name, &name, lang).unwrap_or_else(|err| {
+            state = NewProjectState::InvalidTemplate(err.get_msg().to_string());
+            Template {
+                files: vec![],
+                config: Config::new(&name, lang),
+            }
+        });
+
         // Create Action Arguments
         let project_root = path::PathBuf::from(&name);
 
-        let config_string = serde_json::to_string_pretty(&Config::new(&name, lang)).unwrap();
-        let config_path = project_root.join("cproj.json");
+        let config_string = format.serialize(&template.config);
+        let config_path = project_root.join(format.file_name());
         let config_contents = config_string.as_bytes();
 
-        let entry_point = match lang {
-            Lang::C => project_root.join("src").join("main.c"),
-            Lang::Cpp => project_root.join("src").join("main.cpp"),
-        };
-        let entry_contents = b"int main(int argc, char *argv[])\n{\n\treturn 0;\n}\n";
-
         // Create Action Chain
         let mut action_chain = ActionChain::new();
 
@@ -63,9 +84,30 @@ impl NewProject {
             .add(CreateDirectory::new(project_root.join("bin/debug/log")))
             .add(CreateDirectory::new(project_root.join("bin/debug/obj")))
             .add(CreateDirectory::new(project_root.join("bin/release/log")))
-            .add(CreateDirectory::new(project_root.join("bin/release/obj")))
-            .add(CreateFile::new(entry_point, entry_contents))
-            .add(CreateFile::new(config_path, config_contents));
+            .add(CreateDirectory::new(project_root.join("bin/release/obj")));
+
+        if vcs == Vcs::Git {
+            action_chain.add(GitInit::new(&project_root, vcs_explicit));
+
+            // a template may bring its own .gitignore
+            let gitignore = path::Path::new(".gitignore");
+            if !template.files.iter().any(|(path, _)| path == gitignore) {
+                action_chain.add(CreateFile::new(
+                    project_root.join(gitignore),
+                    vcs::GITIGNORE.as_bytes(),
+                ));
+            }
+        }
+
+        for (path, contents) in &template.files {
+            let path = project_root.join(path);
+
+            action_chain
+                .add(EnsureDirectory::new(path.parent().unwrap()))
+                .add(CreateFile::new(path, contents));
+        }
+
+        action_chain.add(CreateFile::new(config_path, config_contents));
 
         Box::new(Self {
             action_chain,
@@ -74,7 +116,13 @@ impl NewProject {
     }
 
     fn create_arg_retriever() -> ArgRetriever {
-        let rules = [ArgRule::new("--name", 1), ArgRule::new("--lang", 1)];
+        let mut rules = vec![
+            ArgRule::new("--name", 1),
+            ArgRule::new("--lang", 1),
+            ArgRule::new("--template", 1),
+            ArgRule::new("--format", 1),
+        ];
+        rules.extend(Vcs::arg_rules());
         ArgRetriever::new(&rules)
     }
 
@@ -85,24 +133,32 @@ impl NewProject {
             .or_else(|| arg_ret.get_untagged().first().cloned())
     }
 
+    pub fn get_config_format(arg_ret: &ArgRetriever) -> Option<config_file::Format> {
+        match arg_ret.get_tag_args("--format") {
+            None => Some(config_file::Format::Json),
+            Some(args) => config_file::Format::from_name(&args[0]),
+        }
+    }
+
     fn get_project_lang(arg_ret: &ArgRetriever) -> Option<Lang> {
         match arg_ret.get_tag_args("--lang") {
             None => Some(Lang::C),
-            Some(args) => match args[0].to_lowercase().as_str() {
-                "c" => Some(Lang::C),
-                "cpp" | "c++" => Some(Lang::Cpp),
-                _ => None,
-            },
+            Some(args) => Lang::from_name(&args[0]),
         }
     }
 }
 
 impl Action for NewProject {
     fn execute(&mut self) -> Result<()> {
-        match self.state {
+        match &self.state {
             NewProjectState::ValidArguments => self.action_chain.execute(),
             NewProjectState::InvalidLang => Err("Invalid lang argument".into()),
             NewProjectState::NameNotFound => Err("Project name not passed".into()),
+            NewProjectState::InvalidTemplate(msg) => Err(msg.as_str().into()),
+            NewProjectState::InvalidVcs => Err("Invalid vcs argument, expected git or none".into()),
+            NewProjectState::InvalidFormat => {
+                Err("Invalid format argument, expected json or toml".into())
+            }
         }
         .map_err(|err| err.prepend("Failed to create project: "))
     }
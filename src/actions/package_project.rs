@@ -0,0 +1,65 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    config_file,
+    profile::Profile,
+    ActionChain, BuildProject, CreatePackage, HasFile, ReadConfig,
+};
+
+// cproj package [--source]
+
+pub struct PackageProject {
+    action_chain: Box<ActionChain>,
+}
+
+impl PackageProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        let source = arg_retriever.has_tag("--source");
+
+        // Create Action Arguments
+        let profile = Profile::new(true);
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        // a source package doesnt need anything to be built
+        if source {
+            action_chain.add(HasFile::new(config_file::path()));
+        } else {
+            action_chain.add(BuildProject::with_profile(profile.clone()));
+        }
+
+        action_chain
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(CreatePackage::new(config, profile, source));
+
+        Box::new(Self { action_chain })
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        ArgRetriever::new(&[ArgRule::new("--source", 0)])
+    }
+}
+
+impl Action for PackageProject {
+    fn execute(&mut self) -> Result<()> {
+        self.action_chain
+            .execute()
+            .map_err(|err| err.prepend("Failed to package project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project package: "))
+    }
+}
@@ -1,18 +1,21 @@
-use std::{cell::RefCell, process, rc::Rc};
+use std::{cell::RefCell, rc::Rc};
 
 use crate::{
     action::{Action, Result},
-    arg_retriever::{ArgRetriever, ArgRule},
+    arg_retriever::ArgRetriever,
     config::Config,
-    BuildProject, ReadConfig,
+    crash::CrashReport,
+    profile::Profile,
+    runner::Runner,
+    BuildProject, ReadConfig, RunBinary,
 };
 
+// cproj run [--release, --backtrace, --core-dump] [--backend cproj, ninja] [--runner cmd] [args] [-- args]
+
 pub struct RunProject {
     build_action: Box<BuildProject>,
     fetch_config_action: Box<ReadConfig>,
-    release_mode: bool,
-    config: Rc<RefCell<Config>>,
-    arg_retriever: ArgRetriever,
+    run_action: Box<RunBinary>,
 }
 
 impl RunProject {
@@ -22,50 +25,39 @@ impl RunProject {
         arg_retriever.load(args);
 
         // Get Necessary Arguments
-        let release_mode = Self::release_mode(&arg_retriever);
+        // an invalid profile is reported by the build action before it gets used
+        let profile = Profile::from_args(&arg_retriever).unwrap_or_else(|_| Profile::new(false));
+        let crash_report = CrashReport::from_args(&arg_retriever, &profile);
+        let runner = Runner::from_args(&arg_retriever);
         let config = Rc::new(RefCell::new(Config::new("", crate::config::Lang::C)));
 
+        // args after -- always go to the binary, even ones cproj knows about
+        let bin_args = [arg_retriever.get_untagged(), arg_retriever.get_trailing()].concat();
+
         Box::new(Self {
             build_action: BuildProject::new(args),
             fetch_config_action: ReadConfig::new(Rc::clone(&config)),
-            config,
-            release_mode,
-            arg_retriever,
+            run_action: RunBinary::new(config, profile, bin_args, crash_report, runner),
         })
     }
 
     fn create_arg_retriever() -> ArgRetriever {
-        let rules = [ArgRule::new("--release", 0)];
+        let mut rules = BuildProject::arg_rules();
+        rules.extend(CrashReport::arg_rules());
+        rules.extend(Runner::arg_rules());
         ArgRetriever::new(&rules)
     }
-
-    fn release_mode(arg_ret: &ArgRetriever) -> bool {
-        arg_ret.has_tag("--release")
-    }
 }
 
 impl Action for RunProject {
     fn execute(&mut self) -> Result<()> {
         self.build_action.execute()?;
         self.fetch_config_action.execute()?;
-
-        let bin_path = if self.release_mode {
-            format!("bin/release/{}", &self.config.borrow().name)
-        } else {
-            format!("bin/debug/{}", &self.config.borrow().name)
-        };
-
-        println!("=> Running executable\n");
-
-        let mut bin = process::Command::new(bin_path);
-        bin.args(&self.arg_retriever.get_untagged());
-
-        bin.spawn().unwrap();
-
-        Ok(())
+        self.run_action.execute()
     }
 
     fn undo(&mut self) -> Result<()> {
+        self.run_action.undo()?;
         self.fetch_config_action.undo()?;
         self.build_action.undo()?;
 
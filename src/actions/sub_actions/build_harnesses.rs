@@ -0,0 +1,227 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fs,
+    path::{self, PathBuf},
+    process,
+    rc::Rc,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    entry_point,
+    profile::Profile,
+    CompileFiles,
+};
+
+/// Builds a binary for every source file in a directory outside of src (such as
+/// benches), linking each one against the objects of the project that dont
+/// define main
+///
+/// Expects the project to already be built for `profile`
+///
+pub struct BuildHarnesses {
+    harness_dir: PathBuf,
+    src_paths: Rc<RefCell<HashSet<PathBuf>>>,
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    extra_flags: Vec<String>,
+    names: Vec<String>,
+    harnesses: Rc<RefCell<Vec<(String, PathBuf)>>>,
+}
+
+impl BuildHarnesses {
+    /// `names` limits which harnesses get built, an empty list builds all of
+    /// them. The name and binary path of every built harness are stored in
+    /// `harnesses`
+    pub fn new<P>(
+        harness_dir: P,
+        src_paths: Rc<RefCell<HashSet<PathBuf>>>,
+        config: Rc<RefCell<Config>>,
+        profile: Profile,
+        extra_flags: Vec<String>,
+        names: Vec<String>,
+        harnesses: Rc<RefCell<Vec<(String, PathBuf)>>>,
+    ) -> Box<Self>
+    where
+        P: AsRef<path::Path>,
+    {
+        Box::new(Self {
+            harness_dir: harness_dir.as_ref().to_path_buf(),
+            src_paths,
+            config,
+            profile,
+            extra_flags,
+            names,
+            harnesses,
+        })
+    }
+
+    fn kind(&self) -> String {
+        self.harness_dir
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn get_harness_files(&self) -> Result<Vec<PathBuf>> {
+        let entries = match fs::read_dir(&self.harness_dir) {
+            Ok(val) => val,
+            Err(_) => {
+                return Err(format!(
+                    "Failed to read {}",
+                    self.harness_dir.to_str().unwrap_or("directory")
+                )
+                .into());
+            }
+        };
+
+        let mut files: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && CompileFiles::is_code_file(path))
+            .filter(|path| {
+                let name = path.file_stem().unwrap().to_string_lossy().to_string();
+                self.names.is_empty() || self.names.contains(&name)
+            })
+            .collect();
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// the project objects a harness gets linked against
+    fn get_project_objects(&self) -> Vec<PathBuf> {
+        let src_paths = self.src_paths.borrow();
+        let code_files = src_paths
+            .iter()
+            .filter(|path| CompileFiles::is_code_file(path));
+        let main_file = entry_point::find_main(code_files.clone());
+
+        code_files
+            .filter(|path| Some(*path) != main_file.as_ref())
+            .map(|path| self.profile.obj_path(path))
+            .collect()
+    }
+
+    fn run_logged(&self, mut cmd: process::Command, log_path: &path::Path) -> Result<()> {
+        match fs::File::create(log_path) {
+            Ok(file) => {
+                cmd.stdout(
+                    file.try_clone()
+                        .map_or(process::Stdio::null(), |val| val.into()),
+                );
+                cmd.stderr(process::Stdio::from(file));
+            }
+            Err(_) => {
+                println!(
+                    "    -> failed create log for {}",
+                    log_path.to_str().unwrap()
+                );
+            }
+        }
+
+        match cmd.output() {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(_) => Err(format!("check {} for more info", log_path.to_str().unwrap()).into()),
+            Err(_) => {
+                Err(format!("failed to run {}", self.profile.cc(&self.config.borrow())).into())
+            }
+        }
+    }
+
+    fn build(&self, path: &path::Path, objects: &[PathBuf]) -> Result<PathBuf> {
+        let config = self.config.borrow();
+        let out_dir = self.profile.harness_dir(&self.kind());
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+
+        let bin_path = out_dir.join(&name);
+        let obj_path = out_dir.join(format!("{}.o", name));
+        let log_path = out_dir.join(format!("{}.log", name));
+
+        // compile
+        let mut cc = process::Command::new(self.profile.cc(&config));
+        cc.arg("-c").arg(path).arg("-o").arg(&obj_path);
+
+        cc.arg("-I").arg("src");
+        for inc in &config.include {
+            cc.arg("-I").arg(inc);
+        }
+
+        cc.args(self.profile.file_compile_flags(&config, path));
+        cc.args(&self.extra_flags);
+
+        self.run_logged(cc, &log_path)
+            .map_err(|err| err.prepend(&format!("failed to compile {}, ", name)))?;
+
+        // link
+        let mut link = process::Command::new(self.profile.cc(&config));
+        link.args(self.profile.link_flags(&config));
+        link.arg(&obj_path).args(objects);
+        link.args(self.profile.compile_flags(&config));
+        link.args(&self.extra_flags);
+
+        for lib in &config.lib {
+            link.arg("-L").arg(lib);
+        }
+
+        link.arg("-o").arg(&bin_path);
+
+        self.run_logged(link, &out_dir.join(format!("{}.link.log", name)))
+            .map_err(|err| err.prepend(&format!("failed to link {}, ", name)))?;
+
+        Ok(bin_path)
+    }
+}
+
+impl Action for BuildHarnesses {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Building {}...", self.kind());
+
+        let out_dir = self.profile.harness_dir(&self.kind());
+        if fs::create_dir_all(&out_dir).is_err() {
+            return Err(format!("Failed to create {}", out_dir.to_str().unwrap()).into());
+        }
+
+        let files = self.get_harness_files()?;
+        if files.is_empty() && self.names.is_empty() {
+            return Err(format!(
+                "no source files found in {}",
+                self.harness_dir.to_str().unwrap()
+            )
+            .into());
+        } else if files.is_empty() {
+            return Err(format!(
+                "{} not found in {}",
+                self.names.join(", "),
+                self.harness_dir.to_str().unwrap()
+            )
+            .into());
+        }
+
+        let objects = self.get_project_objects();
+        let mut harnesses = vec![];
+
+        for file in files {
+            println!("    -> building {}", file.to_str().unwrap());
+
+            let bin_path = self.build(&file, &objects).inspect_err(|_| {
+                println!("    -> build failed");
+            })?;
+            let name = file.file_stem().unwrap().to_string_lossy().to_string();
+
+            harnesses.push((name, bin_path));
+        }
+
+        *self.harnesses.borrow_mut() = harnesses;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // anything left behind gets overwritten by the next build
+        self.harnesses.borrow_mut().clear();
+        Ok(())
+    }
+}
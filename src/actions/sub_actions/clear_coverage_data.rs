@@ -0,0 +1,38 @@
+use std::{cell::RefCell, collections::HashSet, fs, path, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    profile::Profile,
+};
+
+/// Removes the coverage data left behind by earlier runs, gcov adds onto
+/// existing data instead of replacing it
+pub struct ClearCoverageData {
+    src_paths: Rc<RefCell<HashSet<path::PathBuf>>>,
+    profile: Profile,
+}
+
+impl ClearCoverageData {
+    pub fn new(src_paths: Rc<RefCell<HashSet<path::PathBuf>>>, profile: Profile) -> Box<Self> {
+        Box::new(Self { src_paths, profile })
+    }
+}
+
+impl Action for ClearCoverageData {
+    fn execute(&mut self) -> Result<()> {
+        for path in self.src_paths.borrow().iter() {
+            let gcda_path = self.profile.gcda_path(path);
+
+            if gcda_path.exists() && fs::remove_file(&gcda_path).is_err() {
+                return Err(format!("Failed to remove {}", gcda_path.to_str().unwrap()).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // the data is regenerated by the next run
+        Ok(())
+    }
+}
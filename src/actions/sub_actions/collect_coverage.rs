@@ -0,0 +1,197 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{self, PathBuf},
+    process,
+    rc::Rc,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    coverage::{self, FileCoverage},
+    profile::Profile,
+    CompileFiles,
+};
+
+/// Collects the gcov data of every file in the project, prints a summary of it
+/// and writes it out as an lcov tracefile and an html report
+pub struct CollectCoverage {
+    src_paths: Rc<RefCell<HashSet<PathBuf>>>,
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    fail_under: Option<f64>,
+}
+
+impl CollectCoverage {
+    pub fn new(
+        src_paths: Rc<RefCell<HashSet<PathBuf>>>,
+        config: Rc<RefCell<Config>>,
+        profile: Profile,
+        fail_under: Option<f64>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            src_paths,
+            config,
+            profile,
+            fail_under,
+        })
+    }
+
+    fn report_dir(&self) -> PathBuf {
+        self.profile.dir().join("coverage")
+    }
+
+    // clang writes its own flavour of the gcov format which only llvm-cov
+    // understands
+    fn gcov_command(&self) -> process::Command {
+        if self.profile.cc(&self.config.borrow()).contains("clang") {
+            let mut cmd = process::Command::new("llvm-cov");
+            cmd.arg("gcov");
+            cmd
+        } else {
+            process::Command::new("gcov")
+        }
+    }
+
+    fn run_gcov(
+        &self,
+        path: &path::Path,
+        report: &mut HashMap<String, FileCoverage>,
+    ) -> Result<()> {
+        let mut gcov = self.gcov_command();
+        gcov.args(["-b", "-c", "-t", "-o"])
+            .arg(self.profile.obj_path(path))
+            .arg(path)
+            .stderr(process::Stdio::null());
+
+        let output = match gcov.output() {
+            Ok(val) => val,
+            Err(_) => return Err("failed to run gcov".into()),
+        };
+
+        if !output.status.success() {
+            return Err(format!("gcov failed on {}", path.to_str().unwrap()).into());
+        }
+
+        coverage::parse_gcov(&String::from_utf8_lossy(&output.stdout), report);
+        Ok(())
+    }
+
+    fn write_reports(&self, report: &BTreeMap<String, FileCoverage>) -> Result<()> {
+        let html_dir = self.report_dir().join("html");
+
+        if fs::create_dir_all(&html_dir).is_err() {
+            return Err(format!("Failed to create {}", html_dir.to_str().unwrap()).into());
+        }
+
+        let lcov_path = self.report_dir().join("lcov.info");
+        let mut files = vec![
+            (lcov_path.clone(), coverage::to_lcov(report)),
+            (html_dir.join("index.html"), coverage::to_html_index(report)),
+        ];
+
+        for (path, file) in report {
+            let code = fs::read_to_string(path).unwrap_or_default();
+            files.push((
+                html_dir.join(coverage::html_page_name(path)),
+                coverage::to_html_page(path, file, &code),
+            ));
+        }
+
+        for (path, contents) in files {
+            if fs::write(&path, contents).is_err() {
+                return Err(format!("Failed to write {}", path.to_str().unwrap()).into());
+            }
+        }
+
+        println!("    -> wrote {}", lcov_path.to_str().unwrap());
+        println!(
+            "    -> wrote {}",
+            html_dir.join("index.html").to_str().unwrap()
+        );
+
+        Ok(())
+    }
+}
+
+impl Action for CollectCoverage {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Collecting Coverage...");
+
+        let mut code_files: Vec<_> = self
+            .src_paths
+            .borrow()
+            .iter()
+            .filter(|path| CompileFiles::is_code_file(path))
+            .cloned()
+            .collect();
+        code_files.sort();
+
+        let mut report = HashMap::new();
+        for path in &code_files {
+            self.run_gcov(path, &mut report)?;
+        }
+
+        // gcov also reports on system headers, only the project is of interest
+        let project_files: HashSet<_> = self
+            .src_paths
+            .borrow()
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let report: BTreeMap<_, _> = report
+            .into_iter()
+            .filter(|(path, _)| project_files.contains(path))
+            .collect();
+
+        let (mut lines, mut lines_hit) = (0, 0);
+        let (mut branches, mut branches_hit) = (0, 0);
+
+        for (path, file) in &report {
+            println!(
+                "    -> {}: lines {:.2}% ({}/{}), branches {:.2}% ({}/{})",
+                path,
+                coverage::percent(file.lines_hit(), file.lines.len()),
+                file.lines_hit(),
+                file.lines.len(),
+                coverage::percent(file.branches_hit(), file.branch_count()),
+                file.branches_hit(),
+                file.branch_count()
+            );
+
+            lines += file.lines.len();
+            lines_hit += file.lines_hit();
+            branches += file.branch_count();
+            branches_hit += file.branches_hit();
+        }
+
+        let line_percent = coverage::percent(lines_hit, lines);
+        println!(
+            "    -> total: lines {:.2}% ({}/{}), branches {:.2}% ({}/{})",
+            line_percent,
+            lines_hit,
+            lines,
+            coverage::percent(branches_hit, branches),
+            branches_hit,
+            branches
+        );
+
+        self.write_reports(&report)?;
+
+        match self.fail_under {
+            Some(threshold) if line_percent < threshold => Err(format!(
+                "line coverage of {:.2}% is under {}%",
+                line_percent, threshold
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // the reports are regenerated by the next run
+        Ok(())
+    }
+}
@@ -10,14 +10,16 @@ use std::{
 
 use crate::{
     action::{Action, Result},
-    config::Config,
+    config::{Config, Kind},
+    profile::Profile,
+    sha256,
 };
 
 pub struct CompileFiles {
     src_files: Rc<RefCell<HashSet<path::PathBuf>>>,
     files_to_compile: Rc<RefCell<HashSet<path::PathBuf>>>,
     config: Rc<RefCell<Config>>,
-    release_mode: bool,
+    profile: Profile,
 }
 
 impl CompileFiles {
@@ -25,13 +27,13 @@ impl CompileFiles {
         src_files: Rc<RefCell<HashSet<path::PathBuf>>>,
         files_to_compile: Rc<RefCell<HashSet<path::PathBuf>>>,
         config: Rc<RefCell<Config>>,
-        release_mode: bool,
+        profile: Profile,
     ) -> Box<Self> {
         Box::new(Self {
             src_files,
             files_to_compile,
             config,
-            release_mode,
+            profile,
         })
     }
 
@@ -40,76 +42,115 @@ impl CompileFiles {
 
         binding
             .iter()
-            .filter(|path| {
-                if let Some(ext) = path.extension() {
-                    let ext = ext.to_str().unwrap();
+            .filter(|path| Self::is_code_file(path))
+            .cloned()
+            .collect()
+    }
 
-                    (ext.contains('c') || ext.contains('i')) && !ext.contains('t')
-                } else {
-                    false
-                }
+    // files that didnt change but whose compile command did, such as when an
+    // override or the flags of the profile were edited
+    fn get_stale_files(&self) -> Vec<path::PathBuf> {
+        let config = self.config.borrow();
+        let files_to_compile = self.files_to_compile.borrow();
+
+        self.src_files
+            .borrow()
+            .iter()
+            .filter(|path| Self::is_code_file(path) && !files_to_compile.contains(*path))
+            .filter(|path| {
+                let args = Self::compile_command(&config, &self.profile, path);
+                let old = fs::read_to_string(self.profile.fingerprint_path(path)).ok();
+                old.as_deref() != Some(Self::fingerprint(&args).as_str())
             })
             .cloned()
             .collect()
     }
 
-    fn convert_to_obj_path(&self, path: &path::Path) -> path::PathBuf {
-        let mode = if self.release_mode {
-            "release"
-        } else {
-            "debug"
-        };
-
-        let output_path = path.strip_prefix(path::PathBuf::from("src")).unwrap();
-        let output_path = output_path.to_str().unwrap().replace('/', "_");
-        let mut obj_path = path::PathBuf::from("bin")
-            .join(mode)
-            .join("obj")
-            .join(output_path);
-        obj_path.set_extension("o");
-        obj_path
+    fn fingerprint(args: &[String]) -> String {
+        sha256::hex_digest(args.join("\0").as_bytes())
     }
 
-    fn convert_to_log_path(&self, path: &path::Path) -> path::PathBuf {
-        let mode = if self.release_mode {
-            "release"
+    /// checks if a path points to a file that gets compiled, as opposed to a
+    /// header file
+    pub fn is_code_file(path: &path::Path) -> bool {
+        if let Some(ext) = path.extension() {
+            let ext = ext.to_str().unwrap();
+
+            (ext.contains('c') || ext.contains('i')) && !ext.contains('t')
         } else {
-            "debug"
-        };
+            false
+        }
+    }
 
-        let output_path = path.strip_prefix(path::PathBuf::from("src")).unwrap();
-        let output_path = output_path.to_str().unwrap().replace('/', "_");
-        let mut log_path = path::PathBuf::from("bin")
-            .join(mode)
-            .join("log")
-            .join(output_path);
-        log_path.set_extension("log");
-        log_path
+    /// the full command line that compiles `path` into its object file
+    pub fn compile_command(config: &Config, profile: &Profile, path: &path::Path) -> Vec<String> {
+        let mut cmd = vec![
+            profile.cc(config),
+            "-c".to_string(),
+            path.to_str().unwrap().to_string(),
+            "-o".to_string(),
+            profile.obj_path(path).to_str().unwrap().to_string(),
+        ];
+
+        for inc in &config.include {
+            cmd.push("-I".to_string());
+            cmd.push(inc.clone());
+        }
+
+        cmd.extend(profile.file_compile_flags(config, path));
+        cmd
     }
 
-    /// compiles a file and outputs the path to the object file
-    fn compile(&self, path: &path::Path) -> Result<path::PathBuf> {
-        let obj_path = self.convert_to_obj_path(path);
+    /// the full command line that links `objects` into the output of the
+    /// profile, for static libraries the archive has to be removed first
+    /// since ar only ever adds to it
+    pub fn link_command(
+        config: &Config,
+        profile: &Profile,
+        objects: &[path::PathBuf],
+    ) -> Vec<String> {
+        let output_path = profile.output_path(config).to_str().unwrap().to_string();
+        let objects = objects
+            .iter()
+            .map(|path| path.to_str().unwrap().to_string());
 
-        let mut cc = process::Command::new(&self.config.borrow().cc);
-        cc.arg("-c");
-        cc.arg(path.to_str().unwrap());
-        cc.arg("-o");
-        cc.arg(&obj_path);
+        if config.kind == Kind::StaticLib {
+            let mut cmd = vec!["ar".to_string(), "rcs".to_string(), output_path];
+            cmd.extend(objects);
+            return cmd;
+        }
 
-        for inc in &self.config.borrow().include {
-            cc.arg("-I");
-            cc.arg(inc);
+        let mut cmd = vec![profile.cc(config)];
+        if config.kind == Kind::SharedLib {
+            cmd.push("-shared".to_string());
         }
+        cmd.extend(profile.link_flags(config));
+        cmd.extend(objects);
+        cmd.extend(profile.compile_flags(config));
 
-        if self.release_mode {
-            cc.args(&self.config.borrow().release_flags);
-        } else {
-            cc.args(&self.config.borrow().debug_flags);
+        for lib in &config.lib {
+            cmd.push("-L".to_string());
+            cmd.push(lib.clone());
         }
 
+        cmd.push("-o".to_string());
+        cmd.push(output_path);
+        cmd
+    }
+
+    /// compiles a file and outputs the path to the object file
+    fn compile(&self, path: &path::Path) -> Result<path::PathBuf> {
+        let obj_path = self.profile.obj_path(path);
+
+        let args = Self::compile_command(&self.config.borrow(), &self.profile, path);
+        let fingerprint_path = self.profile.fingerprint_path(path);
+        let _ = fs::remove_file(&fingerprint_path);
+
+        let mut cc = process::Command::new(&args[0]);
+        cc.args(&args[1..]);
+
         // compile and log
-        let log_path = self.convert_to_log_path(path);
+        let log_path = self.profile.log_path(path);
         let log_file = fs::File::create(&log_path);
 
         match log_file {
@@ -128,60 +169,49 @@ impl CompileFiles {
             }
         };
 
-        let compile_output = cc.output().unwrap(); // if err then the command failed to execute
+        let compile_output = cc
+            .output()
+            .map_err(|_| format!("failed to run {}", args[0]))?;
 
         if !compile_output.status.success() {
             println!("    -> compilation failed");
             Err(format!("failed to compile {}", path.to_str().unwrap()).into())
         } else {
+            let _ = fs::write(fingerprint_path, Self::fingerprint(&args));
             Ok(obj_path)
         }
     }
 
-    fn link(&self) -> Result<()> {
-        println!("    -> linking binary...");
-        let bin_path = if self.release_mode {
-            format!("bin/release/{}", &self.config.borrow().name)
-        } else {
-            format!("bin/debug/{}", &self.config.borrow().name)
-        };
-
-        let mut link = process::Command::new(&self.config.borrow().cc);
-
-        for flg in &self.config.borrow().link_flags {
-            link.arg(flg);
-        }
-
-        // link all objs
-        for path in self.src_files.borrow().iter().filter(|path| {
-            if let Some(ext) = path.extension() {
-                let ext = ext.to_str().unwrap();
+    fn write_timestamp(&self) {
+        let mut timestamp_file = fs::File::create(self.profile.timestamp_path()).unwrap();
+        timestamp_file.write_all(b"This file holds the mtime of the last successful compile\nIf you edit this file, you must delete it otherwise cproj might not detect some changes in the project").unwrap();
+    }
 
-                (ext.contains('c') || ext.contains('i')) && !ext.contains('t')
-            } else {
-                false
-            }
-        }) {
-            link.arg(self.convert_to_obj_path(path));
-        }
+    fn link(&self) -> Result<()> {
+        let config = self.config.borrow();
 
-        if self.release_mode {
-            link.args(&self.config.borrow().release_flags);
+        if config.kind == Kind::StaticLib {
+            println!("    -> archiving library...");
+            let _ = fs::remove_file(self.profile.output_path(&config));
+        } else if config.kind.is_lib() {
+            println!("    -> linking library...");
         } else {
-            link.args(&self.config.borrow().debug_flags);
+            println!("    -> linking binary...");
         }
 
-        for lib in &self.config.borrow().lib {
-            link.arg("-L").arg(lib);
-        }
+        let objects: Vec<_> = self
+            .src_files
+            .borrow()
+            .iter()
+            .filter(|path| Self::is_code_file(path))
+            .map(|path| self.profile.obj_path(path))
+            .collect();
 
-        link.arg("-o").arg(bin_path);
+        let args = Self::link_command(&config, &self.profile, &objects);
+        let mut link = process::Command::new(&args[0]);
+        link.args(&args[1..]);
 
-        let log_path = if self.release_mode {
-            "bin/release/log/linker.log"
-        } else {
-            "bin/debug/log/linker.log"
-        };
+        let log_path = self.profile.linker_log_path();
 
         let log_file = fs::File::create(log_path);
 
@@ -198,7 +228,9 @@ impl CompileFiles {
             }
         };
 
-        let link_output = link.output().unwrap(); // if err then the command failed to execute
+        let link_output = link
+            .output()
+            .map_err(|_| format!("failed to run {}, is it installed?", args[0]))?;
 
         if !link_output.status.success() {
             println!("    -> linking failed");
@@ -214,7 +246,15 @@ impl Action for CompileFiles {
         println!("=> Compiling...");
 
         // get just the source files without header files
-        let code_files: Vec<_> = self.get_code_files();
+        let mut code_files: Vec<_> = self.get_code_files();
+
+        for file in self.get_stale_files() {
+            println!(
+                "    -> compile command of {} changed",
+                file.to_str().unwrap()
+            );
+            code_files.push(file);
+        }
 
         let mut compile_results = vec![];
 
@@ -243,20 +283,15 @@ impl Action for CompileFiles {
             }
         }
 
-        // link to final binary
-        if let Err(err) = self.link() {
+        // link to final binary, a check has nothing to link
+        if self.profile.is_check() {
+            self.write_timestamp();
+            Ok(())
+        } else if let Err(err) = self.link() {
             println!("    -> failed to link binary");
             Err(err)
         } else {
-            let timestamp = if self.release_mode {
-                "bin/release/timestamp"
-            } else {
-                "bin/debug/timestamp"
-            };
-
-            let mut timestamp_file = fs::File::create(timestamp).unwrap();
-            timestamp_file.write_all(b"This file holds the mtime of the last successful compile\nIf you edit this file, you must delete it otherwise cproj might not detect some changes in the project").unwrap();
-
+            self.write_timestamp();
             Ok(())
         }
     }
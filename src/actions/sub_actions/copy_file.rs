@@ -0,0 +1,91 @@
+use std::{fs, path};
+
+use crate::action::{Action, Result};
+
+/// Copies a file, creating the parent directories of the destination if they
+/// are missing and overwriting the destination if it already exists
+///
+/// Undoing restores the file that was overwritten, or removes the copy along
+/// with any directories that were created for it
+///
+pub struct CopyFile {
+    from: path::PathBuf,
+    to: path::PathBuf,
+    // the contents of the file that was overwritten
+    overwritten: Option<Vec<u8>>,
+    created_dirs: Vec<path::PathBuf>,
+    copied: bool,
+}
+
+impl CopyFile {
+    pub fn new<P, Q>(from: P, to: Q) -> Box<Self>
+    where
+        P: AsRef<path::Path>,
+        Q: AsRef<path::Path>,
+    {
+        Box::new(Self {
+            from: from.as_ref().to_path_buf(),
+            to: to.as_ref().to_path_buf(),
+            overwritten: None,
+            created_dirs: vec![],
+            copied: false,
+        })
+    }
+}
+
+impl Action for CopyFile {
+    fn execute(&mut self) -> Result<()> {
+        if let Some(parent) = self.to.parent() {
+            self.created_dirs = parent
+                .ancestors()
+                .filter(|path| !path.as_os_str().is_empty() && !path.exists())
+                .map(|path| path.to_path_buf())
+                .collect();
+
+            if fs::create_dir_all(parent).is_err() {
+                return Err(format!("Failed to create {}", parent.to_str().unwrap()).into());
+            }
+        }
+
+        if self.to.is_file() {
+            self.overwritten = fs::read(&self.to).ok();
+        }
+
+        if fs::copy(&self.from, &self.to).is_err() {
+            Err(format!(
+                "Failed to copy {} to {}",
+                self.from.to_str().unwrap(),
+                self.to.to_str().unwrap()
+            )
+            .into())
+        } else {
+            self.copied = true;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        if self.copied {
+            let res = match self.overwritten.take() {
+                Some(contents) => fs::write(&self.to, contents),
+                None => fs::remove_file(&self.to),
+            };
+
+            if res.is_err() {
+                return Err(format!("Failed to restore {}", self.to.to_str().unwrap()).into());
+            }
+
+            self.copied = false;
+        }
+
+        // deepest first, a directory that isnt empty means its parents arent
+        // either
+        for dir in self.created_dirs.drain(..) {
+            if fs::remove_dir(&dir).is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    cell::RefCell,
+    env, fs,
+    path::{self, PathBuf},
+    process,
+    rc::Rc,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    config_file,
+    profile::Profile,
+    sha256, InstallFiles,
+};
+
+/// Creates dist/[name]-[version]-[arch].tar.gz out of the outputs of a profile,
+/// laid out the same way cproj install lays them out, along with a sha256
+/// checksum of it
+///
+/// A source package is named [name]-[version]-src.tar.gz instead and holds src,
+/// include and cproj.json. Either holds the license and readme of the project
+///
+pub struct CreatePackage {
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    source: bool,
+    written: Vec<PathBuf>,
+}
+
+impl CreatePackage {
+    pub fn new(config: Rc<RefCell<Config>>, profile: Profile, source: bool) -> Box<Self> {
+        Box::new(Self {
+            config,
+            profile,
+            source,
+            written: vec![],
+        })
+    }
+
+    fn dist_dir() -> PathBuf {
+        PathBuf::from("dist")
+    }
+
+    fn stage_dir() -> PathBuf {
+        PathBuf::from("bin").join("package")
+    }
+
+    // cross compilers report the architecture they build for
+    fn target_arch(&self) -> String {
+        process::Command::new(self.profile.cc(&self.config.borrow()))
+            .arg("-dumpmachine")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .split('-')
+                    .next()
+                    .map(|arch| arch.trim().to_string())
+            })
+            .filter(|arch| !arch.is_empty())
+            .unwrap_or_else(|| env::consts::ARCH.to_string())
+    }
+
+    fn package_name(&self) -> String {
+        let config = self.config.borrow();
+        let version = config.package.clone().unwrap_or_default().version;
+
+        if self.source {
+            format!("{}-{}-src", config.name, version)
+        } else {
+            format!("{}-{}-{}", config.name, version, self.target_arch())
+        }
+    }
+
+    // the file set in the config, or the first of `candidates` that exists
+    fn find_doc(configured: Option<String>, candidates: &[&str]) -> Result<Option<PathBuf>> {
+        match configured {
+            Some(doc) if path::Path::new(&doc).is_file() => Ok(Some(PathBuf::from(doc))),
+            Some(doc) => Err(format!("{} does not exist", doc).into()),
+            None => Ok(candidates
+                .iter()
+                .map(PathBuf::from)
+                .find(|path| path.is_file())),
+        }
+    }
+
+    fn walk(dir: &path::Path, files: &mut Vec<PathBuf>) {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_dir() {
+                    Self::walk(&path, files);
+                } else {
+                    files.push(path);
+                }
+            }
+        }
+    }
+
+    /// (file in the project, where it goes in the staging directory)
+    fn get_files(&self, root: &path::Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let config = self.config.borrow();
+        let package = config.package.clone().unwrap_or_default();
+
+        let mut files = if self.source {
+            let mut paths = vec![config_file::path()];
+            Self::walk(path::Path::new("src"), &mut paths);
+            Self::walk(path::Path::new("include"), &mut paths);
+
+            paths
+                .into_iter()
+                .map(|path| (path.clone(), root.join(path)))
+                .collect()
+        } else {
+            InstallFiles::layout(&config, &self.profile, root)?
+        };
+
+        let docs = [
+            Self::find_doc(
+                package.license,
+                &["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"],
+            )?,
+            Self::find_doc(package.readme, &["README.md", "README", "README.txt"])?,
+        ];
+
+        for doc in docs.into_iter().flatten() {
+            let name = doc.file_name().unwrap().to_os_string();
+            files.push((doc, root.join(name)));
+        }
+
+        Ok(files)
+    }
+
+    fn stage(files: &[(PathBuf, PathBuf)]) -> Result<()> {
+        for (from, to) in files {
+            let copied = fs::create_dir_all(to.parent().unwrap()).and_then(|_| fs::copy(from, to));
+
+            if copied.is_err() {
+                return Err(format!("Failed to stage {}", from.to_str().unwrap()).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_archive(&mut self, package_name: &str) -> Result<PathBuf> {
+        if fs::create_dir_all(Self::dist_dir()).is_err() {
+            return Err(format!("Failed to create {}", Self::dist_dir().to_str().unwrap()).into());
+        }
+
+        let archive_path = Self::dist_dir().join(format!("{}.tar.gz", package_name));
+
+        let status = process::Command::new("tar")
+            .arg("-czf")
+            .arg(&archive_path)
+            .arg("-C")
+            .arg(Self::stage_dir())
+            .arg(package_name)
+            .status()
+            .map_err(|_| "failed to run tar")?;
+
+        if !status.success() {
+            return Err(format!("tar failed to create {}", archive_path.to_str().unwrap()).into());
+        }
+
+        self.written.push(archive_path.clone());
+        Ok(archive_path)
+    }
+
+    // in the format of sha256sum, so that sha256sum -c can check it
+    fn write_checksum(&mut self, archive_path: &path::Path) -> Result<PathBuf> {
+        let archive = fs::read(archive_path)
+            .map_err(|_| format!("Failed to read {}", archive_path.to_str().unwrap()))?;
+
+        let checksum_path = PathBuf::from(format!("{}.sha256", archive_path.to_str().unwrap()));
+        let checksum = format!(
+            "{}  {}\n",
+            sha256::hex_digest(&archive),
+            archive_path.file_name().unwrap().to_str().unwrap()
+        );
+
+        if fs::write(&checksum_path, checksum).is_err() {
+            return Err(format!("Failed to write {}", checksum_path.to_str().unwrap()).into());
+        }
+
+        self.written.push(checksum_path.clone());
+        Ok(checksum_path)
+    }
+}
+
+impl Action for CreatePackage {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Packaging...");
+
+        let package_name = self.package_name();
+        let root = Self::stage_dir().join(&package_name);
+
+        // leftovers of an earlier package must not end up in this one
+        let _ = fs::remove_dir_all(Self::stage_dir());
+
+        let files = self.get_files(&root)?;
+        for (_, to) in &files {
+            println!(
+                "    -> {}",
+                to.strip_prefix(Self::stage_dir())
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+            );
+        }
+
+        Self::stage(&files)?;
+        let archive_path = self.write_archive(&package_name);
+        let _ = fs::remove_dir_all(Self::stage_dir());
+        let archive_path = archive_path?;
+
+        let checksum_path = self.write_checksum(&archive_path)?;
+
+        println!("    -> wrote {}", archive_path.to_str().unwrap());
+        println!("    -> wrote {}", checksum_path.to_str().unwrap());
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let _ = fs::remove_dir_all(Self::stage_dir());
+
+        for path in self.written.drain(..) {
+            if fs::remove_file(&path).is_err() {
+                return Err(format!("Failed to remove {}", path.to_str().unwrap()).into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use std::{fs, path};
+
+use crate::{
+    action::{Action, Result},
+    config_edit::{self, Edit},
+    config_file, WriteFile,
+};
+
+/// Changes one field of the project config, the config is only written if
+/// it is still valid afterwards
+///
+pub struct EditConfig {
+    field: String,
+    edit: Edit,
+    write_action: Option<Box<WriteFile>>,
+}
+
+impl EditConfig {
+    pub fn new(field: &str, edit: Edit) -> Box<Self> {
+        Box::new(Self {
+            field: field.to_string(),
+            edit,
+            write_action: None,
+        })
+    }
+}
+
+impl Action for EditConfig {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Editing Config...");
+
+        let (path, format) = config_file::locate(path::Path::new("."))?;
+        let file_name = format.file_name();
+        let contents =
+            fs::read_to_string(&path).map_err(|_| format!("failed to read {}", file_name))?;
+
+        let edited = config_edit::apply(format, &contents, &self.field, &self.edit)?;
+
+        let write_action = self
+            .write_action
+            .insert(WriteFile::new(&path, edited.as_bytes()));
+        write_action.execute()?;
+
+        match &self.edit {
+            Edit::Set(values) => println!("    -> set {} to {}", self.field, values.join(" ")),
+            Edit::Add(values) => println!("    -> added {} to {}", values.join(" "), self.field),
+            Edit::Remove(values) if values.is_empty() => println!("    -> removed {}", self.field),
+            Edit::Remove(values) => {
+                println!("    -> removed {} from {}", values.join(" "), self.field)
+            }
+        }
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.write_action.as_mut() {
+            Some(write_action) => write_action.undo(),
+            None => Ok(()),
+        }
+    }
+}
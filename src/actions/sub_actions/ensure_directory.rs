@@ -0,0 +1,76 @@
+use std::{fs, path};
+
+use crate::action::{Action, Result};
+
+/// Creates a directory along with any missing parents, unlike
+/// [`CreateDirectory`](crate::CreateDirectory) it is not an error for the
+/// directory to already exist
+///
+/// Undoing only removes the directories that were created by this action and
+/// are still empty, anything put into them since then is left alone
+///
+pub struct EnsureDirectory {
+    path: path::PathBuf,
+    created: Vec<path::PathBuf>,
+}
+
+impl EnsureDirectory {
+    pub fn new<P>(path: P) -> Box<Self>
+    where
+        P: AsRef<path::Path>,
+    {
+        Box::new(Self {
+            path: path.as_ref().to_path_buf(),
+            created: vec![],
+        })
+    }
+}
+
+impl Action for EnsureDirectory {
+    fn execute(&mut self) -> Result<()> {
+        // remember which directories are missing so undo can leave the rest
+        let missing: Vec<_> = self
+            .path
+            .ancestors()
+            .filter(|path| !path.as_os_str().is_empty() && !path.exists())
+            .map(|path| path.to_path_buf())
+            .collect();
+
+        if fs::create_dir_all(&self.path).is_err() || !self.path.is_dir() {
+            Err(format!(
+                "Failed to create {}",
+                self.path.to_str().unwrap_or("directory")
+            )
+            .into())
+        } else {
+            self.created = missing;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // ancestors go from the deepest directory up, so children are removed
+        // before their parents
+        while let Some(path) = self.created.first() {
+            let is_empty = fs::read_dir(path)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+
+            // its parents cant be empty either
+            if !is_empty {
+                self.created.clear();
+                break;
+            }
+
+            if fs::remove_dir(path).is_err() {
+                return Err(
+                    format!("Failed to remove {}", path.to_str().unwrap_or("directory")).into(),
+                );
+            }
+
+            self.created.remove(0);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fs,
+    path::{self, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    export::Format,
+    CompileFiles, WriteFile,
+};
+
+/// Generates the build definition of another build system, such as
+/// CMakeLists.txt, from the config and the source files of the project
+/// # Error
+/// If the file already exists and wasnt generated by cproj, unless `force` is
+/// set, since it is most likely written by hand
+///
+pub struct ExportBuildFile {
+    format: Format,
+    src_paths: Rc<RefCell<HashSet<path::PathBuf>>>,
+    config: Rc<RefCell<Config>>,
+    force: bool,
+    write_action: Option<Box<WriteFile>>,
+}
+
+impl ExportBuildFile {
+    pub fn new(
+        format: Format,
+        src_paths: Rc<RefCell<HashSet<path::PathBuf>>>,
+        config: Rc<RefCell<Config>>,
+        force: bool,
+    ) -> Box<Self> {
+        Box::new(Self {
+            format,
+            src_paths,
+            config,
+            force,
+            write_action: None,
+        })
+    }
+}
+
+impl Action for ExportBuildFile {
+    fn execute(&mut self) -> Result<()> {
+        let file_name = self.format.file_name();
+        println!("=> Exporting {}...", file_name);
+
+        let is_generated = |contents: String| contents.starts_with("# Generated by cproj");
+        if !self.force && fs::read_to_string(file_name).is_ok_and(|val| !is_generated(val)) {
+            return Err(format!(
+                "{} wasnt generated by cproj, pass --force to replace it",
+                file_name
+            )
+            .into());
+        }
+
+        let mut sources: Vec<PathBuf> = self
+            .src_paths
+            .borrow()
+            .iter()
+            .filter(|path| CompileFiles::is_code_file(path))
+            .cloned()
+            .collect();
+        sources.sort();
+
+        let contents = self.format.generate(&self.config.borrow(), &sources);
+
+        let write_action = self
+            .write_action
+            .insert(WriteFile::new(self.format.file_name(), contents.as_bytes()));
+        write_action.execute()?;
+
+        println!("    -> wrote {}", self.format.file_name());
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.write_action.as_mut() {
+            Some(write_action) => write_action.undo(),
+            None => Ok(()),
+        }
+    }
+}
@@ -10,6 +10,7 @@ use std::{
 use crate::{
     action::{Action, Result},
     graph::Graph,
+    profile::Profile,
 };
 
 /// Gets the changed files and their dependencies
@@ -18,7 +19,7 @@ pub struct FetchEditedFiles {
     src_files: Rc<RefCell<HashSet<path::PathBuf>>>,
     dependency_graph: Rc<RefCell<Graph<path::PathBuf>>>,
     old_changed_files: Option<HashSet<path::PathBuf>>,
-    release_mode: bool,
+    profile: Profile,
 }
 
 impl FetchEditedFiles {
@@ -26,14 +27,14 @@ impl FetchEditedFiles {
         src_files: Rc<RefCell<HashSet<path::PathBuf>>>,
         dependency_graph: Rc<RefCell<Graph<path::PathBuf>>>,
         changed_files: Rc<RefCell<HashSet<path::PathBuf>>>,
-        release_mode: bool,
+        profile: Profile,
     ) -> Box<Self> {
         Box::new(Self {
             changed_files,
             src_files,
             dependency_graph,
             old_changed_files: None,
-            release_mode,
+            profile,
         })
     }
 
@@ -64,13 +65,7 @@ impl Action for FetchEditedFiles {
         self.old_changed_files = Some(self.changed_files.borrow().clone());
         self.changed_files.borrow_mut().clear();
 
-        let timestamp_path = if self.release_mode {
-            "bin/release/timestamp"
-        } else {
-            "bin/debug/timestamp"
-        };
-
-        let compile_mtime = fs::metadata(timestamp_path)
+        let compile_mtime = fs::metadata(self.profile.timestamp_path())
             .map(|meta| meta.modified().unwrap_or(time::UNIX_EPOCH))
             .unwrap_or(time::UNIX_EPOCH);
 
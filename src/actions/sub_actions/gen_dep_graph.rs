@@ -1,4 +1,10 @@
-use std::{cell::RefCell, collections::HashSet, fs, path, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fs, path,
+    rc::Rc,
+    time,
+};
 
 use crate::{
     action::{Action, Result},
@@ -9,6 +15,9 @@ pub struct GenDepGraph {
     src_paths: Rc<RefCell<HashSet<path::PathBuf>>>,
     dep_graph: Rc<RefCell<Graph<path::PathBuf>>>,
     old_graph: Option<Graph<path::PathBuf>>,
+    // the includes found in each file the last time it was read, so files
+    // that havent changed since then dont get read again
+    include_cache: HashMap<path::PathBuf, (time::SystemTime, Vec<String>)>,
 }
 
 impl GenDepGraph {
@@ -20,6 +29,7 @@ impl GenDepGraph {
             src_paths,
             dep_graph,
             old_graph: None,
+            include_cache: HashMap::new(),
         })
     }
 
@@ -62,8 +72,16 @@ impl GenDepGraph {
         Ok(())
     }
 
-    fn generate_node_edges(&self, entry: &path::Path) -> Result<()> {
-        println!("    -> dependencies of {}", entry.to_str().unwrap());
+    fn get_cached_include_paths(&mut self, entry: &path::Path) -> Result<Vec<String>> {
+        let mtime = fs::metadata(entry).and_then(|meta| meta.modified()).ok();
+
+        if let (Some(mtime), Some((cached_mtime, includes))) =
+            (mtime, self.include_cache.get(entry))
+        {
+            if mtime == *cached_mtime {
+                return Ok(includes.clone());
+            }
+        }
 
         // Get src code from file
         let code = match fs::read_to_string(entry) {
@@ -74,8 +92,21 @@ impl GenDepGraph {
             }
         };
 
+        let includes = self.get_include_paths(&code);
+
+        if let Some(mtime) = mtime {
+            self.include_cache
+                .insert(entry.to_path_buf(), (mtime, includes.clone()));
+        }
+
+        Ok(includes)
+    }
+
+    fn generate_node_edges(&mut self, entry: &path::Path) -> Result<()> {
+        println!("    -> dependencies of {}", entry.to_str().unwrap());
+
         // get included files
-        let included_files: Vec<_> = self.get_include_paths(&code);
+        let included_files: Vec<_> = self.get_cached_include_paths(entry)?;
 
         for path in &included_files {
             // path of the included file relative to the project root
@@ -116,8 +147,9 @@ impl Action for GenDepGraph {
 
         self.create_nodes()?;
 
-        for entry in self.src_paths.borrow().iter() {
-            self.generate_node_edges(entry)?;
+        let src_paths: Vec<_> = self.src_paths.borrow().iter().cloned().collect();
+        for entry in src_paths {
+            self.generate_node_edges(&entry)?;
         }
 
         Ok(())
@@ -0,0 +1,174 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fmt::Write,
+    fs,
+    path::{self, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::{Config, Kind},
+    config_file,
+    profile::Profile,
+    shell_words, CompileFiles,
+};
+
+/// Writes bin/[profile]/build.ninja, which holds every compile and link
+/// command that [`CompileFiles`] would run for the profile
+///
+/// Headers are tracked through the depfiles gcc writes, so unlike with the
+/// cproj backend no dependency graph is needed
+///
+pub struct GenNinjaFile {
+    src_paths: Rc<RefCell<HashSet<PathBuf>>>,
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+}
+
+impl GenNinjaFile {
+    pub fn new(
+        src_paths: Rc<RefCell<HashSet<PathBuf>>>,
+        config: Rc<RefCell<Config>>,
+        profile: Profile,
+    ) -> Box<Self> {
+        Box::new(Self {
+            src_paths,
+            config,
+            profile,
+        })
+    }
+
+    pub fn ninja_file_path(profile: &Profile) -> PathBuf {
+        profile.dir().join("build.ninja")
+    }
+
+    // paths in build statements also end at spaces and colons
+    fn escape_path(path: &path::Path) -> String {
+        path.to_str()
+            .unwrap()
+            .replace('$', "$$")
+            .replace(' ', "$ ")
+            .replace(':', "$:")
+    }
+
+    // commands go through the shell, so each argument is quoted for it before
+    // being escaped for ninja
+    fn escape_command(cmd: &[String]) -> String {
+        cmd.iter()
+            .map(|arg| shell_words::quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('$', "$$")
+    }
+
+    fn generate(&self) -> String {
+        let config = self.config.borrow();
+        let mut output = String::new();
+
+        let mut code_files: Vec<_> = self
+            .src_paths
+            .borrow()
+            .iter()
+            .filter(|path| CompileFiles::is_code_file(path))
+            .cloned()
+            .collect();
+        code_files.sort();
+
+        writeln!(
+            output,
+            "# Generated by cproj build --backend ninja from {}\n",
+            config_file::path().to_str().unwrap()
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "builddir = {}\n",
+            self.profile.dir().to_str().unwrap()
+        )
+        .unwrap();
+
+        writeln!(output, "rule cc").unwrap();
+        writeln!(output, "  command = $cmd -MD -MF $out.d").unwrap();
+        writeln!(output, "  depfile = $out.d").unwrap();
+        writeln!(output, "  deps = gcc").unwrap();
+        writeln!(output, "  restat = 1").unwrap();
+        writeln!(output, "  description = compiling $in\n").unwrap();
+
+        writeln!(output, "rule link").unwrap();
+        writeln!(output, "  command = $cmd").unwrap();
+        writeln!(output, "  description = linking $out\n").unwrap();
+
+        for path in &code_files {
+            let cmd = CompileFiles::compile_command(&config, &self.profile, path);
+
+            writeln!(
+                output,
+                "build {}: cc {}",
+                Self::escape_path(&self.profile.obj_path(path)),
+                Self::escape_path(path)
+            )
+            .unwrap();
+            writeln!(output, "  cmd = {}\n", Self::escape_command(&cmd)).unwrap();
+        }
+
+        let objects: Vec<_> = code_files
+            .iter()
+            .map(|path| self.profile.obj_path(path))
+            .collect();
+        let output_path = self.profile.output_path(&config);
+
+        let mut cmd = Self::escape_command(&CompileFiles::link_command(
+            &config,
+            &self.profile,
+            &objects,
+        ));
+        if config.kind == Kind::StaticLib {
+            cmd = format!("rm -f $out && {}", cmd);
+        }
+
+        writeln!(
+            output,
+            "build {}: link {}",
+            Self::escape_path(&output_path),
+            objects
+                .iter()
+                .map(|path| Self::escape_path(path))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+        .unwrap();
+        writeln!(output, "  cmd = {}\n", cmd).unwrap();
+
+        writeln!(output, "default {}", Self::escape_path(&output_path)).unwrap();
+
+        output
+    }
+}
+
+impl Action for GenNinjaFile {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Generating build.ninja...");
+
+        let ninja_file_path = Self::ninja_file_path(&self.profile);
+
+        // rewriting an unchanged file would make ninja reread it for nothing
+        let contents = self.generate();
+        if fs::read_to_string(&ninja_file_path).is_ok_and(|old| old == contents) {
+            return Ok(());
+        }
+
+        if fs::write(&ninja_file_path, contents).is_err() {
+            Err(format!("Failed to write {}", ninja_file_path.to_str().unwrap()).into())
+        } else {
+            println!("    -> wrote {}", ninja_file_path.to_str().unwrap());
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // the file is regenerated on every build
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use std::{cell::RefCell, collections::HashSet, fs, path, rc::Rc};
 use crate::{
     action::{Action, Result},
     config::{Config, Lang},
+    CompileFiles,
 };
 
 pub struct GenSrcPaths {
@@ -25,7 +26,8 @@ impl GenSrcPaths {
         })
     }
 
-    fn get_extensions(lang: Lang) -> Vec<String> {
+    /// the extensions of the source and header files of a language
+    pub fn get_extensions(lang: Lang) -> Vec<String> {
         match lang {
             Lang::C => ["c", "i", "h"]
                 .iter_mut()
@@ -74,6 +76,7 @@ impl Action for GenSrcPaths {
         self.old_src_paths = Some(self.src_paths.borrow().clone());
 
         self.src_exts = Self::get_extensions(self.config.borrow().lang);
+        let config = self.config.borrow();
 
         *self.src_paths.borrow_mut() = self
             .get_file_paths()?
@@ -87,9 +90,16 @@ impl Action for GenSrcPaths {
                     false
                 }
             })
-            .map(|path| {
+            // headers stay so that changes to them are still tracked
+            .filter(|path| {
+                let is_excluded = CompileFiles::is_code_file(path) && config.is_excluded(path);
+                if is_excluded {
+                    println!("    -> excluded {}", path.to_str().unwrap());
+                }
+                !is_excluded
+            })
+            .inspect(|path| {
                 println!("    -> found {}", path.as_path().to_str().unwrap());
-                path
             })
             .collect();
 
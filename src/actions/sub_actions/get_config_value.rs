@@ -0,0 +1,48 @@
+use std::{fs, path};
+
+use crate::{
+    action::{Action, Result},
+    config_edit, config_file,
+};
+
+/// Prints the value of a field of the project config as it is written, on
+/// its own so that scripts can use it. Lists are printed one item per line
+///
+pub struct GetConfigValue {
+    field: String,
+}
+
+impl GetConfigValue {
+    pub fn new(field: &str) -> Box<Self> {
+        Box::new(Self {
+            field: field.to_string(),
+        })
+    }
+}
+
+impl Action for GetConfigValue {
+    fn execute(&mut self) -> Result<()> {
+        let (path, format) = config_file::locate(path::Path::new("."))?;
+        let contents = fs::read_to_string(path)
+            .map_err(|_| format!("failed to read {}", format.file_name()))?;
+
+        match config_edit::get(format, &contents, &self.field)? {
+            serde_json::Value::String(value) => println!("{}", value),
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    match value.as_str() {
+                        Some(value) => println!("{}", value),
+                        None => println!("{}", value),
+                    }
+                }
+            }
+            value => println!("{}", serde_json::to_string_pretty(&value).unwrap()),
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    fs,
+    path::{self, PathBuf},
+    process,
+};
+
+use crate::action::{Action, Result};
+
+/// Runs git init in a directory, unless it is already inside of a git
+/// repository
+///
+/// If git isnt installed this is only an error when `required` is set,
+/// otherwise the repository is skipped. Undoing removes the repository that
+/// was created
+///
+pub struct GitInit {
+    path: PathBuf,
+    required: bool,
+    created: bool,
+}
+
+impl GitInit {
+    pub fn new<P>(path: P, required: bool) -> Box<Self>
+    where
+        P: AsRef<path::Path>,
+    {
+        Box::new(Self {
+            path: path.as_ref().to_path_buf(),
+            required,
+            created: false,
+        })
+    }
+}
+
+impl Action for GitInit {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Initializing Git Repository...");
+
+        let inside = process::Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .stderr(process::Stdio::null())
+            .output();
+
+        match inside {
+            Err(_) if self.required => return Err("failed to run git, is it installed?".into()),
+            Err(_) => {
+                println!("    -> git is not installed, skipping");
+                return Ok(());
+            }
+            Ok(output) if output.status.success() => {
+                println!("    -> already inside of a git repository, skipping");
+                return Ok(());
+            }
+            Ok(_) => {}
+        }
+
+        let status = process::Command::new("git")
+            .args(["init", "-q"])
+            .arg(&self.path)
+            .status()
+            .map_err(|_| "failed to run git")?;
+
+        if status.success() {
+            self.created = true;
+            Ok(())
+        } else {
+            Err(format!(
+                "git init failed in {}",
+                self.path.to_str().unwrap_or("directory")
+            )
+            .into())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        if !self.created {
+            return Ok(());
+        }
+
+        let git_dir = self.path.join(".git");
+        if fs::remove_dir_all(&git_dir).is_err() {
+            Err(format!("Failed to remove {}", git_dir.to_str().unwrap()).into())
+        } else {
+            self.created = false;
+            Ok(())
+        }
+    }
+}
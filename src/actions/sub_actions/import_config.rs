@@ -0,0 +1,68 @@
+use std::path;
+
+use crate::{
+    action::{Action, Result},
+    config_file,
+    import::{self, Source},
+    CreateFile,
+};
+
+/// Writes a cproj.json worked out from the commands of another build, and
+/// reports the flags that couldnt be carried over
+///
+pub struct ImportConfig {
+    source: Source,
+    name: String,
+    create_action: Option<Box<CreateFile>>,
+}
+
+impl ImportConfig {
+    pub fn new(source: Source, name: &str) -> Box<Self> {
+        Box::new(Self {
+            source,
+            name: name.to_string(),
+            create_action: None,
+        })
+    }
+}
+
+impl Action for ImportConfig {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Importing {}...", self.source.describe());
+
+        if config_file::exists(path::Path::new(".")) {
+            return Err("the project already has a cproj.json or cproj.toml".into());
+        }
+
+        let commands = self.source.commands()?;
+        let imported = import::import(&self.name, &commands)?;
+
+        for source in &imported.sources {
+            println!("    -> found {}", source.to_str().unwrap());
+        }
+
+        if !imported.unmapped.is_empty() {
+            println!("=> Flags That Could Not Be Mapped...");
+            for flag in &imported.unmapped {
+                println!("    -> {}", flag);
+            }
+        }
+
+        let config_string = serde_json::to_string_pretty(&imported.config).unwrap();
+
+        let create_action = self
+            .create_action
+            .insert(CreateFile::new("cproj.json", config_string.as_bytes()));
+        create_action.execute()?;
+
+        println!("    -> wrote cproj.json");
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.create_action.as_mut() {
+            Some(create_action) => create_action.undo(),
+            None => Ok(()),
+        }
+    }
+}
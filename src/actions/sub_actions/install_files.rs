@@ -0,0 +1,186 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    env, fs,
+    path::{self, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    profile::Profile,
+    ActionChain, CopyFile,
+};
+
+/// Copies the output of a profile into a prefix, along with the public headers
+/// of libraries, and records every installed file in the install manifest
+///
+/// Binaries go to [prefix]/bin, libraries to [prefix]/lib and the headers in
+/// the include directory of the project to [prefix]/include/[name]. When the
+/// DESTDIR environment variable is set, the prefix is placed inside of it
+///
+pub struct InstallFiles {
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    prefix: PathBuf,
+    copies: Box<ActionChain>,
+    // the manifest from before installing, None if there wasnt one
+    old_manifest: Option<Option<String>>,
+}
+
+impl InstallFiles {
+    pub fn new(config: Rc<RefCell<Config>>, profile: Profile, prefix: PathBuf) -> Box<Self> {
+        Box::new(Self {
+            config,
+            profile,
+            prefix,
+            copies: ActionChain::new(),
+            old_manifest: None,
+        })
+    }
+
+    /// lists every file that has been installed from the project, one path per
+    /// line
+    pub fn manifest_path() -> PathBuf {
+        PathBuf::from("install_manifest.txt")
+    }
+
+    // DESTDIR stages an install somewhere other than the root of the system,
+    // such as for packaging
+    fn install_root(&self) -> PathBuf {
+        match env::var_os("DESTDIR").filter(|val| !val.is_empty()) {
+            Some(dest_dir) => {
+                let relative = self.prefix.strip_prefix("/").unwrap_or(&self.prefix);
+                PathBuf::from(dest_dir).join(relative)
+            }
+            None => self.prefix.clone(),
+        }
+    }
+
+    fn find_headers(dir: &path::Path, headers: &mut Vec<PathBuf>) {
+        let entries = match fs::read_dir(dir) {
+            Ok(val) => val,
+            Err(_) => return,
+        };
+
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.is_dir() {
+                Self::find_headers(&path, headers);
+            } else if path
+                .extension()
+                .is_some_and(|ext| ext.to_str().unwrap().starts_with(['h', 'H']))
+            {
+                headers.push(path);
+            }
+        }
+    }
+
+    /// Lays out the outputs of a profile inside of `root` the way they are
+    /// installed, as (file in the project, where it goes)
+    pub fn layout(
+        config: &Config,
+        profile: &Profile,
+        root: &path::Path,
+    ) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let output_path = profile.output_path(config);
+
+        if !output_path.is_file() {
+            return Err(format!("{} was not built", output_path.to_str().unwrap()).into());
+        }
+
+        let dir = if config.kind.is_lib() { "lib" } else { "bin" };
+        let mut files = vec![(
+            output_path.clone(),
+            root.join(dir).join(output_path.file_name().unwrap()),
+        )];
+
+        if config.kind.is_lib() {
+            let include_dir = path::Path::new("include");
+            let mut headers = vec![];
+            Self::find_headers(include_dir, &mut headers);
+            headers.sort();
+
+            for header in headers {
+                let relative = header.strip_prefix(include_dir).unwrap().to_path_buf();
+                files.push((
+                    header,
+                    root.join("include").join(&config.name).join(relative),
+                ));
+            }
+        }
+
+        Ok(files)
+    }
+
+    fn get_files(&self) -> Result<Vec<(PathBuf, PathBuf)>> {
+        Self::layout(&self.config.borrow(), &self.profile, &self.install_root())
+    }
+
+    fn write_manifest(&mut self, installed: &[PathBuf]) -> Result<()> {
+        let old_manifest = fs::read_to_string(Self::manifest_path()).ok();
+
+        // installing the debug and release builds shouldnt forget either of them
+        let mut entries: BTreeSet<_> = old_manifest
+            .iter()
+            .flat_map(|manifest| manifest.lines())
+            .map(|line| line.to_string())
+            .collect();
+        entries.extend(
+            installed
+                .iter()
+                .map(|path| path.to_string_lossy().to_string()),
+        );
+
+        let manifest: String = entries.iter().map(|line| format!("{}\n", line)).collect();
+
+        if fs::write(Self::manifest_path(), manifest).is_err() {
+            return Err(format!(
+                "Failed to write {}",
+                Self::manifest_path().to_str().unwrap()
+            )
+            .into());
+        }
+
+        self.old_manifest = Some(old_manifest);
+        Ok(())
+    }
+}
+
+impl Action for InstallFiles {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Installing...");
+
+        let files = self.get_files()?;
+
+        // absolute paths keep the manifest valid no matter where uninstall
+        // runs from
+        let installed: Vec<_> = files
+            .iter()
+            .map(|(_, to)| env::current_dir().map_or(to.clone(), |dir| dir.join(to)))
+            .collect();
+
+        self.copies = ActionChain::new();
+        for (from, to) in files {
+            println!("    -> {}", to.to_str().unwrap());
+            self.copies.add(CopyFile::new(from, to));
+        }
+
+        self.copies.execute()?;
+        self.write_manifest(&installed)
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.old_manifest.take() {
+            Some(Some(manifest)) => {
+                let _ = fs::write(Self::manifest_path(), manifest);
+            }
+            Some(None) => {
+                let _ = fs::remove_file(Self::manifest_path());
+            }
+            None => {}
+        }
+
+        self.copies.undo()
+    }
+}
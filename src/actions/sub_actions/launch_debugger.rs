@@ -0,0 +1,152 @@
+use std::{cell::RefCell, env, process, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    profile::Profile,
+};
+
+/// Starts gdb or lldb on the project binary of a profile, with the working
+/// directory, source paths, breakpoints and arguments of the binary already set
+///
+/// The debugger is picked from `debugger`, then the `debugger` field of the
+/// config and otherwise whichever of gdb and lldb is installed
+///
+pub struct LaunchDebugger {
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    debugger: Option<String>,
+    breakpoints: Vec<String>,
+    args: Vec<String>,
+}
+
+impl LaunchDebugger {
+    pub fn new(
+        config: Rc<RefCell<Config>>,
+        profile: Profile,
+        debugger: Option<String>,
+        breakpoints: Vec<String>,
+        args: Vec<String>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            config,
+            profile,
+            debugger,
+            breakpoints,
+            args,
+        })
+    }
+
+    fn is_installed(program: &str) -> bool {
+        process::Command::new(program)
+            .arg("--version")
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .status()
+            .is_ok()
+    }
+
+    fn get_debugger(&self) -> Result<String> {
+        if let Some(debugger) = self
+            .debugger
+            .clone()
+            .or_else(|| self.config.borrow().debugger.clone())
+        {
+            return Ok(debugger);
+        }
+
+        ["gdb", "lldb"]
+            .into_iter()
+            .find(|debugger| Self::is_installed(debugger))
+            .map(|debugger| debugger.to_string())
+            .ok_or_else(|| "neither gdb nor lldb were found".into())
+    }
+
+    fn gdb_command(&self, debugger: &str) -> process::Command {
+        let config = self.config.borrow();
+        let mut cmd = process::Command::new(debugger);
+        cmd.arg("-q");
+
+        // include is also searched so that stepping into headers shows source
+        for dir in std::iter::once(&"src".to_string()).chain(config.include.iter()) {
+            cmd.args(["-ex", &format!("directory {}", dir)]);
+        }
+
+        for breakpoint in &self.breakpoints {
+            cmd.args(["-ex", &format!("break {}", breakpoint)]);
+        }
+
+        cmd.arg("--args")
+            .arg(self.profile.bin_path(&config))
+            .args(&self.args);
+        cmd
+    }
+
+    fn lldb_command(&self, debugger: &str) -> process::Command {
+        let config = self.config.borrow();
+        let mut cmd = process::Command::new(debugger);
+
+        if let Ok(dir) = env::current_dir() {
+            cmd.args([
+                "-o",
+                &format!(
+                    "settings set target.process.working-dir {}",
+                    dir.to_str().unwrap()
+                ),
+            ]);
+        }
+
+        for breakpoint in &self.breakpoints {
+            let (file, line) = breakpoint.rsplit_once(':').unwrap();
+            cmd.args([
+                "-o",
+                &format!("breakpoint set --file {} --line {}", file, line),
+            ]);
+        }
+
+        cmd.arg("--")
+            .arg(self.profile.bin_path(&config))
+            .args(&self.args);
+        cmd
+    }
+}
+
+impl Action for LaunchDebugger {
+    fn execute(&mut self) -> Result<()> {
+        let debugger = self.get_debugger()?;
+
+        println!("=> Starting {}...", debugger);
+
+        let config = self.config.borrow().clone();
+        if !self
+            .profile
+            .compile_flags(&config)
+            .iter()
+            .any(|flag| flag.starts_with("-g"))
+        {
+            println!(
+                "    -> profiles.debug.flags has no -g, the debugger won't be able to show source"
+            );
+        }
+
+        let mut cmd = if debugger.contains("lldb") {
+            self.lldb_command(&debugger)
+        } else {
+            self.gdb_command(&debugger)
+        };
+
+        // the binary runs from the project root, just like with cproj run
+        if let Ok(dir) = env::current_dir() {
+            cmd.current_dir(dir);
+        }
+
+        cmd.status()
+            .map_err(|_| format!("failed to start {}", debugger))?;
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
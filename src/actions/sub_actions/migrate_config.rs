@@ -0,0 +1,67 @@
+use std::{fs, path};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    config_edit, config_file, WriteFile,
+};
+
+/// Rewrites the config of the project in the current layout, keeping what it
+/// holds and the format it is written in
+///
+pub struct MigrateConfig {
+    write_action: Option<Box<WriteFile>>,
+}
+
+impl MigrateConfig {
+    pub fn new() -> Box<Self> {
+        Box::new(Self { write_action: None })
+    }
+}
+
+impl Action for MigrateConfig {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Migrating Config...");
+
+        let (path, format) = config_file::locate(path::Path::new("."))?;
+        let file_name = format.file_name();
+
+        let contents =
+            fs::read_to_string(&path).map_err(|_| format!("failed to read {}", file_name))?;
+        let mut value = format
+            .parse_value(&contents)
+            .map_err(|err| err.prepend(&format!("failed to parse {}: ", file_name)))?;
+
+        let Some(version) = config_file::migrate(&mut value)? else {
+            println!("    -> already at version {}", Config::VERSION);
+            return Ok(());
+        };
+
+        // nothing is written unless the migrated config is valid
+        config_file::from_value(value.clone())
+            .map_err(|err| err.prepend(&format!("the migrated {} is invalid: ", file_name)))?;
+
+        // only the fields that moved are rewritten, keeping the comments of
+        // the others
+        let migrated = config_edit::rewrite(format, &contents, &value, &config_file::MOVED_FIELDS)?;
+        let write_action = self
+            .write_action
+            .insert(WriteFile::new(&path, migrated.as_bytes()));
+        write_action.execute()?;
+
+        println!(
+            "    -> migrated {} from version {} to version {}",
+            file_name,
+            version,
+            Config::VERSION
+        );
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.write_action.as_mut() {
+            Some(write_action) => write_action.undo(),
+            None => Ok(()),
+        }
+    }
+}
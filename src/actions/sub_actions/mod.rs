@@ -1,19 +1,65 @@
+mod build_harnesses;
+mod clear_coverage_data;
 mod clear_directory;
+mod collect_coverage;
 mod compile_files;
+mod copy_file;
 mod create_directory;
 mod create_file;
+mod create_package;
+mod edit_config;
+mod ensure_directory;
+mod export_build_file;
 mod fetch_edited_files;
 mod gen_dep_graph;
+mod gen_ninja_file;
 mod gen_src_paths;
+mod get_config_value;
+mod git_init;
 mod has_file;
+mod import_config;
+mod install_files;
+mod launch_debugger;
+mod migrate_config;
 mod read_config;
+mod run_benchmarks;
+mod run_binary;
+mod run_fuzzer;
+mod run_ninja;
+mod run_test_cases;
+mod show_config;
+mod uninstall_files;
+mod write_file;
 
+pub use build_harnesses::BuildHarnesses;
+pub use clear_coverage_data::ClearCoverageData;
 pub use clear_directory::ClearDirectory;
+pub use collect_coverage::CollectCoverage;
 pub use compile_files::CompileFiles;
+pub use copy_file::CopyFile;
 pub use create_directory::CreateDirectory;
 pub use create_file::CreateFile;
+pub use create_package::CreatePackage;
+pub use edit_config::EditConfig;
+pub use ensure_directory::EnsureDirectory;
+pub use export_build_file::ExportBuildFile;
 pub use fetch_edited_files::FetchEditedFiles;
 pub use gen_dep_graph::GenDepGraph;
+pub use gen_ninja_file::GenNinjaFile;
 pub use gen_src_paths::GenSrcPaths;
+pub use get_config_value::GetConfigValue;
+pub use git_init::GitInit;
 pub use has_file::HasFile;
+pub use import_config::ImportConfig;
+pub use install_files::InstallFiles;
+pub use launch_debugger::LaunchDebugger;
+pub use migrate_config::MigrateConfig;
 pub use read_config::ReadConfig;
+pub use run_benchmarks::RunBenchmarks;
+pub use run_binary::RunBinary;
+pub use run_fuzzer::RunFuzzer;
+pub use run_ninja::RunNinja;
+pub use run_test_cases::RunTestCases;
+pub use show_config::ShowConfig;
+pub use uninstall_files::UninstallFiles;
+pub use write_file::WriteFile;
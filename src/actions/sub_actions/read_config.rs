@@ -1,22 +1,40 @@
-use std::{cell::RefCell, fs, path, rc::Rc};
+use std::{cell::RefCell, fs, path, rc::Rc, time};
 
 use crate::{
     action::{Action, Result},
     config::Config,
+    config_file,
+    effective::{self, Origins, UserConfig},
 };
 
+/// Reads the project config and layers the user config and the environment
+/// on top of it, see [`effective::resolve()`]
+///
 pub struct ReadConfig {
     config: Rc<RefCell<Config>>,
+    origins: Option<Rc<RefCell<Origins>>>,
     old_config: Option<Config>,
+    // which configs were loaded and when they were last modified, lets
+    // repeated executions skip parsing configs that havent changed
+    loaded_mtimes: Option<Vec<(path::PathBuf, time::SystemTime)>>,
 }
 
 impl ReadConfig {
     pub fn new(config: Rc<RefCell<Config>>) -> Box<Self> {
         Box::new(Self {
             config,
+            origins: None,
             old_config: None,
+            loaded_mtimes: None,
         })
     }
+
+    /// also shares where each value of the config came from
+    pub fn with_origins(config: Rc<RefCell<Config>>, origins: Rc<RefCell<Origins>>) -> Box<Self> {
+        let mut read_config = Self::new(config);
+        read_config.origins = Some(origins);
+        read_config
+    }
 }
 
 impl Action for ReadConfig {
@@ -24,25 +42,69 @@ impl Action for ReadConfig {
         println!("=> Reading Project Config...");
 
         self.old_config = Some(self.config.borrow().clone());
-        let config_root = path::PathBuf::from("cproj.json");
+        let (config_root, format) = match config_file::locate(path::Path::new(".")) {
+            Ok(val) => val,
+            Err(err) => {
+                println!("    -> {}", err.get_msg());
+                return Err("Failed to find config".into());
+            }
+        };
+        let file_name = format.file_name();
+
+        let mtimes = [Some(config_root.clone()), UserConfig::path()]
+            .into_iter()
+            .flatten()
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let mtime = fs::metadata(&path).and_then(|meta| meta.modified());
+                mtime.map(|mtime| (path, mtime)).ok()
+            })
+            .collect::<Option<Vec<_>>>();
+        if mtimes.is_some() && mtimes == self.loaded_mtimes {
+            return Ok(());
+        }
 
-        let config_string = match fs::read_to_string(config_root) {
+        let config_string = match fs::read_to_string(&config_root) {
             Ok(val) => val,
             Err(_) => {
-                println!("    -> failed to read cproj.json");
+                println!("    -> failed to read {}", file_name);
                 return Err("Failed to read config".into());
             }
         };
 
-        let config = match serde_json::from_str(&config_string) {
+        let config = match format.parse(&config_string) {
             Ok(val) => val,
-            Err(_) => {
-                println!("    -> failed to parse cproj.json");
+            Err(err) => {
+                println!("    -> failed to parse {}", file_name);
+                println!("    -> {}", err.get_msg());
                 return Err("Failed to parse config".into());
             }
         };
 
+        let user_config = match UserConfig::load() {
+            Ok(val) => val,
+            Err(err) => {
+                println!("    -> {}", err.get_msg());
+                return Err("Failed to read user config".into());
+            }
+        };
+
+        let fields = format.written_fields(&config_string);
+        let (config, origins) =
+            match effective::resolve(config, &fields, file_name, user_config.as_ref()) {
+                Ok(val) => val,
+                Err(err) => {
+                    println!("    -> failed to apply the environment to {}", file_name);
+                    println!("    -> {}", err.get_msg());
+                    return Err("Failed to resolve config".into());
+                }
+            };
+
         *self.config.borrow_mut() = config;
+        if let Some(shared) = &self.origins {
+            *shared.borrow_mut() = origins;
+        }
+        self.loaded_mtimes = mtimes;
         Ok(())
     }
 
@@ -50,6 +112,7 @@ impl Action for ReadConfig {
         if self.old_config.is_some() {
             *self.config.borrow_mut() = self.old_config.take().unwrap();
         }
+        self.loaded_mtimes = None;
         Ok(())
     }
 }
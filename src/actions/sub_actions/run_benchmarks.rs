@@ -0,0 +1,202 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    path::{self, PathBuf},
+    process,
+    rc::Rc,
+    time,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::{Action, Result};
+
+/// The timings of a single benchmark, in nanoseconds
+#[derive(Clone, Serialize, Deserialize)]
+struct BenchResult {
+    mean: f64,
+    variance: f64,
+    iterations: usize,
+}
+
+impl BenchResult {
+    fn from_samples(samples: &[f64]) -> Self {
+        let iterations = samples.len();
+        let mean = samples.iter().sum::<f64>() / iterations as f64;
+
+        // sample variance, a single run has none
+        let variance = if iterations > 1 {
+            samples.iter().map(|val| (val - mean).powi(2)).sum::<f64>() / (iterations - 1) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            mean,
+            variance,
+            iterations,
+        }
+    }
+
+    fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// Runs each benchmark binary several times and reports how long they took,
+/// optionally saving the results as a baseline or comparing against one
+pub struct RunBenchmarks {
+    harnesses: Rc<RefCell<Vec<(String, PathBuf)>>>,
+    iterations: usize,
+    save_baseline: Option<String>,
+    baseline: Option<String>,
+}
+
+impl RunBenchmarks {
+    pub fn new(
+        harnesses: Rc<RefCell<Vec<(String, PathBuf)>>>,
+        iterations: usize,
+        save_baseline: Option<String>,
+        baseline: Option<String>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            harnesses,
+            iterations,
+            save_baseline,
+            baseline,
+        })
+    }
+
+    fn baseline_path(name: &str) -> PathBuf {
+        path::PathBuf::from("bin")
+            .join("baselines")
+            .join(format!("{}.json", name))
+    }
+
+    fn load_baseline(name: &str) -> Result<BTreeMap<String, BenchResult>> {
+        let path = Self::baseline_path(name);
+
+        let contents = fs::read_to_string(&path)
+            .map_err(|_| format!("baseline {} not found at {}", name, path.to_str().unwrap()))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|_| format!("failed to parse {}", path.to_str().unwrap()).into())
+    }
+
+    fn save(name: &str, results: &BTreeMap<String, BenchResult>) -> Result<()> {
+        let path = Self::baseline_path(name);
+
+        if fs::create_dir_all(path.parent().unwrap()).is_err() {
+            return Err("failed to create the baseline directory".into());
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(results).unwrap())
+            .map_err(|_| format!("failed to write {}", path.to_str().unwrap()).into())
+    }
+
+    fn time(&self, name: &str, bin_path: &path::Path) -> Result<BenchResult> {
+        let mut samples = vec![];
+
+        for _ in 0..self.iterations {
+            let mut bin = process::Command::new(bin_path);
+            bin.stdout(process::Stdio::null());
+
+            let start = time::Instant::now();
+            let status = bin
+                .status()
+                .map_err(|_| format!("failed to run {}", bin_path.to_str().unwrap()))?;
+            let elapsed = start.elapsed();
+
+            if !status.success() {
+                return Err(format!("benchmark {} failed with {}", name, status).into());
+            }
+
+            samples.push(elapsed.as_nanos() as f64);
+        }
+
+        Ok(BenchResult::from_samples(&samples))
+    }
+
+    fn compare(result: &BenchResult, base: &BenchResult) -> String {
+        let change = (result.mean - base.mean) / base.mean * 100.0;
+
+        // changes within the noise of both runs arent worth calling out
+        let noise = result.std_dev() + base.std_dev();
+        let verdict = if (result.mean - base.mean).abs() <= noise {
+            "no significant change"
+        } else if change > 0.0 {
+            "regressed"
+        } else {
+            "improved"
+        };
+
+        format!("{:+.2}% ({})", change, verdict)
+    }
+}
+
+// picks a unit that keeps the mean readable, returns the unit and how many
+// nanoseconds are in it
+fn time_unit(ns: f64) -> (&'static str, f64) {
+    if ns >= 1e9 {
+        ("s", 1e9)
+    } else if ns >= 1e6 {
+        ("ms", 1e6)
+    } else if ns >= 1e3 {
+        ("us", 1e3)
+    } else {
+        ("ns", 1.0)
+    }
+}
+
+impl Action for RunBenchmarks {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Running Benchmarks...");
+
+        let baseline = match &self.baseline {
+            Some(name) => Some(Self::load_baseline(name)?),
+            None => None,
+        };
+
+        let mut results = BTreeMap::new();
+
+        for (name, bin_path) in self.harnesses.borrow().iter() {
+            let result = self.time(name, bin_path)?;
+
+            let (unit, scale) = time_unit(result.mean);
+            println!(
+                "    -> {}: mean {:.3}{unit}, variance {:.3}{unit}² (± {:.3}{unit}) over {} runs",
+                name,
+                result.mean / scale,
+                result.variance / (scale * scale),
+                result.std_dev() / scale,
+                result.iterations
+            );
+
+            if let Some(base) = baseline.as_ref().and_then(|val| val.get(name)) {
+                println!(
+                    "        - {} against baseline {}",
+                    Self::compare(&result, base),
+                    self.baseline.as_ref().unwrap()
+                );
+            } else if baseline.is_some() {
+                println!("        - not found in the baseline");
+            }
+
+            results.insert(name.clone(), result);
+        }
+
+        if let Some(name) = &self.save_baseline {
+            Self::save(name, &results)?;
+            println!("    -> saved baseline {}", name);
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // saving the baseline is the last thing this does, so there is never
+        // anything to undo
+        Ok(())
+    }
+}
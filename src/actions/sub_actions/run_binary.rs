@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc, time::SystemTime};
+
+use crate::{
+    action::{Action, Error, Result},
+    config::Config,
+    crash::{self, CrashReport},
+    profile::Profile,
+    runner::Runner,
+};
+
+/// Runs the project binary of a profile and waits for it to finish
+/// # Error
+/// If the binary fails to start or exits unsuccessfully then
+/// [`RunBinary::execute()`] will return an error, which carries the exit code
+/// of the binary (or 128 + the signal that killed it, like a shell would)
+///
+pub struct RunBinary {
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    args: Vec<String>,
+    crash_report: CrashReport,
+    runner: Option<String>,
+}
+
+impl RunBinary {
+    pub fn new(
+        config: Rc<RefCell<Config>>,
+        profile: Profile,
+        args: Vec<String>,
+        crash_report: CrashReport,
+        runner: Option<String>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            config,
+            profile,
+            args,
+            crash_report,
+            runner,
+        })
+    }
+}
+
+impl Action for RunBinary {
+    fn execute(&mut self) -> Result<()> {
+        let bin_path = self.profile.bin_path(&self.config.borrow());
+
+        let runner = Runner::resolve(self.runner.as_deref(), &self.config.borrow(), &self.profile)?;
+
+        println!("=> Running executable");
+        if let Some(runner) = &runner {
+            println!("    -> running through {}", runner.describe());
+        }
+        println!();
+
+        let mut bin = Runner::command(runner.as_ref(), &bin_path);
+        bin.args(&self.args);
+        self.crash_report.prepare(&mut bin);
+
+        let started = SystemTime::now();
+        let mut child = bin
+            .spawn()
+            .map_err(|_| format!("failed to run {}", bin.get_program().to_string_lossy()))?;
+        let status = child
+            .wait()
+            .map_err(|_| format!("failed to wait for {}", bin_path.to_str().unwrap()))?;
+
+        self.crash_report
+            .report(&bin_path, &self.args, None, status, child.id(), started);
+
+        let bin_name = bin_path.to_str().unwrap();
+
+        if status.success() {
+            Ok(())
+        } else if let Some(signal) = crash::signal(&status) {
+            Err(Error::with_exit_code(
+                &format!(
+                    "{} was terminated by {}",
+                    bin_name,
+                    crash::describe_signal(signal)
+                ),
+                128 + signal,
+            ))
+        } else {
+            let code = status.code().unwrap_or(1);
+            Err(Error::with_exit_code(
+                &format!("{} exited with status {}", bin_name, code),
+                code,
+            ))
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use std::{cell::RefCell, path, process, rc::Rc};
+
+use crate::action::{Action, Result};
+
+/// Runs a libFuzzer target against its corpus, saving any crashing inputs to
+/// the artifact directory
+pub struct RunFuzzer {
+    harnesses: Rc<RefCell<Vec<(String, path::PathBuf)>>>,
+    corpus_dir: path::PathBuf,
+    artifact_dir: path::PathBuf,
+    fuzzer_args: Vec<String>,
+}
+
+impl RunFuzzer {
+    pub fn new<P, Q>(
+        harnesses: Rc<RefCell<Vec<(String, path::PathBuf)>>>,
+        corpus_dir: P,
+        artifact_dir: Q,
+        fuzzer_args: Vec<String>,
+    ) -> Box<Self>
+    where
+        P: AsRef<path::Path>,
+        Q: AsRef<path::Path>,
+    {
+        Box::new(Self {
+            harnesses,
+            corpus_dir: corpus_dir.as_ref().to_path_buf(),
+            artifact_dir: artifact_dir.as_ref().to_path_buf(),
+            fuzzer_args,
+        })
+    }
+}
+
+impl Action for RunFuzzer {
+    fn execute(&mut self) -> Result<()> {
+        let harnesses = self.harnesses.borrow();
+        let (name, bin_path) = match harnesses.first() {
+            Some(val) => val,
+            None => return Err("no fuzz target was built".into()),
+        };
+
+        println!("=> Fuzzing {}\n", name);
+
+        // libFuzzer wants the prefix to end with a separator to treat it as a
+        // directory
+        let artifact_prefix = format!("{}/", self.artifact_dir.to_str().unwrap());
+
+        let status = process::Command::new(bin_path)
+            .arg(format!("-artifact_prefix={}", artifact_prefix))
+            .args(&self.fuzzer_args)
+            .arg(&self.corpus_dir)
+            .status()
+            .map_err(|_| format!("failed to run {}", bin_path.to_str().unwrap()))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} exited with {}, crashing inputs are saved in {}",
+                name,
+                status,
+                self.artifact_dir.to_str().unwrap()
+            )
+            .into())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // the corpus and any artifacts are worth keeping
+        Ok(())
+    }
+}
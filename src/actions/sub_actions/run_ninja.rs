@@ -0,0 +1,40 @@
+use std::process;
+
+use crate::{
+    action::{Action, Result},
+    profile::Profile,
+    GenNinjaFile,
+};
+
+/// Builds a profile by running ninja on its build.ninja
+pub struct RunNinja {
+    profile: Profile,
+}
+
+impl RunNinja {
+    pub fn new(profile: Profile) -> Box<Self> {
+        Box::new(Self { profile })
+    }
+}
+
+impl Action for RunNinja {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Running ninja...");
+
+        let status = process::Command::new("ninja")
+            .arg("-f")
+            .arg(GenNinjaFile::ninja_file_path(&self.profile))
+            .status()
+            .map_err(|_| "failed to run ninja, is it installed?")?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err("ninja failed to build the project".into())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
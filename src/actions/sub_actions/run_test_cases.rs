@@ -0,0 +1,309 @@
+use std::{
+    cell::RefCell,
+    fs,
+    io::Write,
+    path::{self, PathBuf},
+    process,
+    rc::Rc,
+    thread,
+    time::SystemTime,
+};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    crash::{self, CrashReport},
+    diff,
+    profile::Profile,
+    runner::Runner,
+    shell_words,
+};
+
+/// A single golden output test, made up of tests/cases/[name].in and its
+/// sibling files
+struct TestCase {
+    name: String,
+    input_path: PathBuf,
+}
+
+impl TestCase {
+    fn sibling(&self, ext: &str) -> PathBuf {
+        self.input_path.with_extension(ext)
+    }
+
+    // quoted like a shell would, so that an argument can hold spaces
+    fn args(&self) -> Result<Vec<String>> {
+        match fs::read_to_string(self.sibling("args")) {
+            Err(_) => Ok(vec![]),
+            Ok(args) => shell_words::split(&args).map_err(|err| {
+                err.prepend(&format!(
+                    "invalid arguments in {}: ",
+                    self.sibling("args").to_str().unwrap()
+                ))
+            }),
+        }
+    }
+
+    fn expected_exit_code(&self) -> Result<i32> {
+        match fs::read_to_string(self.sibling("exitcode")) {
+            Err(_) => Ok(0),
+            Ok(code) => code.trim().parse().map_err(|_| {
+                format!(
+                    "invalid exit code in {}",
+                    self.sibling("exitcode").to_str().unwrap()
+                )
+                .into()
+            }),
+        }
+    }
+}
+
+/// The output of a binary that was run against a test case
+struct TestOutput {
+    stdout: String,
+    stderr: String,
+    exit_code: Option<i32>,
+    status: process::ExitStatus,
+    // kept around to find the core dump of a crashed binary
+    pid: u32,
+    started: SystemTime,
+}
+
+impl TestOutput {
+    // "terminated by SIGSEGV (segmentation fault)"
+    fn termination(&self) -> String {
+        match crash::signal(&self.status) {
+            Some(signal) => format!("terminated by {}", crash::describe_signal(signal)),
+            None => "terminated".to_string(),
+        }
+    }
+}
+
+/// Runs the project binary against every test case in tests/cases and
+/// compares its output against the expected output
+pub struct RunTestCases {
+    config: Rc<RefCell<Config>>,
+    profile: Profile,
+    bless: bool,
+    filters: Vec<String>,
+    crash_report: CrashReport,
+    runner: Option<String>,
+}
+
+impl RunTestCases {
+    pub fn new(
+        config: Rc<RefCell<Config>>,
+        profile: Profile,
+        bless: bool,
+        filters: Vec<String>,
+        crash_report: CrashReport,
+        runner: Option<String>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            config,
+            profile,
+            bless,
+            filters,
+            crash_report,
+            runner,
+        })
+    }
+
+    pub fn cases_dir() -> PathBuf {
+        path::PathBuf::from("tests").join("cases")
+    }
+
+    fn get_cases(&self) -> Result<Vec<TestCase>> {
+        let entries = match fs::read_dir(Self::cases_dir()) {
+            Ok(val) => val,
+            Err(_) => {
+                return Err(
+                    format!("Failed to read {}", Self::cases_dir().to_str().unwrap()).into(),
+                );
+            }
+        };
+
+        let mut cases: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+            .map(|path| TestCase {
+                name: path.file_stem().unwrap().to_string_lossy().to_string(),
+                input_path: path,
+            })
+            .filter(|case| self.filters.is_empty() || self.filters.contains(&case.name))
+            .collect();
+
+        cases.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(cases)
+    }
+
+    fn run_case(&self, case: &TestCase, runner: Option<&Runner>) -> Result<TestOutput> {
+        let input = fs::read(&case.input_path)
+            .map_err(|_| format!("failed to read {}", case.input_path.to_str().unwrap()))?;
+
+        let bin_path = self.profile.bin_path(&self.config.borrow());
+
+        let mut bin = Runner::command(runner, &bin_path);
+        bin.args(case.args()?)
+            .stdin(process::Stdio::piped())
+            .stdout(process::Stdio::piped())
+            .stderr(process::Stdio::piped());
+        self.crash_report.prepare(&mut bin);
+
+        let started = SystemTime::now();
+        let mut child = bin
+            .spawn()
+            .map_err(|_| format!("failed to run {}", bin.get_program().to_string_lossy()))?;
+
+        // the input is written from another thread so that a binary that
+        // doesnt read all of its input cant deadlock us
+        let pid = child.id();
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = thread::spawn(move || {
+            // a binary closing stdin early is not a failure of the test itself
+            let _ = stdin.write_all(&input);
+        });
+
+        let output = child
+            .wait_with_output()
+            .map_err(|_| format!("failed to wait for {}", bin_path.to_str().unwrap()))?;
+        let _ = writer.join();
+
+        Ok(TestOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.status.code(),
+            status: output.status,
+            pid,
+            started,
+        })
+    }
+
+    fn bless_case(&self, case: &TestCase, output: &TestOutput) -> Result<()> {
+        let exit_code = match output.exit_code {
+            Some(val) => val,
+            None => return Err(format!("{} was {}", case.name, output.termination()).into()),
+        };
+
+        fs::write(case.sibling("out"), &output.stdout)
+            .map_err(|_| format!("failed to write {}", case.sibling("out").to_str().unwrap()))?;
+
+        // only keep an exitcode file around if it says something
+        if exit_code != 0 || case.sibling("exitcode").exists() {
+            fs::write(case.sibling("exitcode"), format!("{}\n", exit_code)).map_err(|_| {
+                format!(
+                    "failed to write {}",
+                    case.sibling("exitcode").to_str().unwrap()
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// returns whether or not the case passed
+    fn check_case(&self, case: &TestCase, output: &TestOutput) -> Result<bool> {
+        let expected_out = fs::read_to_string(case.sibling("out"))
+            .map_err(|_| format!("{} is missing its .out, run cproj test --bless", case.name))?;
+        let expected_code = case.expected_exit_code()?;
+
+        let mut passed = true;
+
+        if output.exit_code != Some(expected_code) {
+            passed = false;
+            match output.exit_code {
+                Some(code) => println!(
+                    "        - expected exit code {}, got {}",
+                    expected_code, code
+                ),
+                None => println!(
+                    "        - expected exit code {}, but the binary was {}",
+                    expected_code,
+                    output.termination()
+                ),
+            }
+        }
+
+        let diff = diff::unified_diff(
+            &expected_out,
+            &output.stdout,
+            case.sibling("out").to_str().unwrap(),
+            "actual output",
+        );
+
+        if !diff.is_empty() {
+            passed = false;
+            println!("        - output differs from the expected output");
+            for line in diff.lines() {
+                println!("          {}", line);
+            }
+        }
+
+        if !passed && !output.stderr.is_empty() {
+            println!("        - stderr:");
+            for line in output.stderr.lines() {
+                println!("          {}", line);
+            }
+        }
+
+        Ok(passed)
+    }
+}
+
+impl Action for RunTestCases {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Running Tests...");
+
+        let cases = self.get_cases()?;
+        let runner = Runner::resolve(self.runner.as_deref(), &self.config.borrow(), &self.profile)?;
+
+        if let Some(runner) = &runner {
+            println!("    -> running through {}", runner.describe());
+        }
+
+        let mut failed = vec![];
+
+        for case in &cases {
+            let output = self.run_case(case, runner.as_ref())?;
+
+            if self.bless {
+                self.bless_case(case, &output)?;
+                println!("    -> blessed {}", case.name);
+            } else if self.check_case(case, &output)? {
+                println!("    -> {} ... ok", case.name);
+            } else {
+                println!("    -> {} ... FAILED", case.name);
+                failed.push(case.name.clone());
+
+                self.crash_report.report(
+                    &self.profile.bin_path(&self.config.borrow()),
+                    &case.args()?,
+                    Some(&case.input_path),
+                    output.status,
+                    output.pid,
+                    output.started,
+                );
+            }
+        }
+
+        if !self.bless {
+            println!(
+                "    -> {} passed, {} failed",
+                cases.len() - failed.len(),
+                failed.len()
+            );
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("test cases failed: {}", failed.join(", ")).into())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // blessed files are meant to stay around
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    config::Config,
+    effective::{self, Origins},
+};
+
+/// Prints every value of the effective config along with where it came from
+pub struct ShowConfig {
+    config: Rc<RefCell<Config>>,
+    origins: Rc<RefCell<Origins>>,
+}
+
+impl ShowConfig {
+    pub fn new(config: Rc<RefCell<Config>>, origins: Rc<RefCell<Origins>>) -> Box<Self> {
+        Box::new(Self { config, origins })
+    }
+}
+
+impl Action for ShowConfig {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Effective Config...");
+
+        let value = serde_json::to_value(&*self.config.borrow()).unwrap();
+        let lines: Vec<_> = self
+            .origins
+            .borrow()
+            .iter()
+            .map(|(field, origins)| {
+                let field_value = match effective::lookup(&value, field).unwrap() {
+                    serde_json::Value::Array(values) => format!(
+                        "[{}]",
+                        values
+                            .iter()
+                            .map(|value| value.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    value => value.to_string(),
+                };
+                let origins: Vec<_> = origins.iter().map(|origin| origin.to_string()).collect();
+                (format!("{} = {}", field, field_value), origins.join(", "))
+            })
+            .collect();
+
+        // lines up the origins so they are easy to scan, long values such as
+        // lists of sections would push them too far out so they dont count
+        let width = lines
+            .iter()
+            .map(|(line, _)| line.len())
+            .filter(|len| *len <= 80)
+            .max()
+            .unwrap_or(0);
+        for (line, origins) in lines {
+            println!("    -> {:width$}  ({})", line, origins, width = width);
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::{fs, path};
+
+use crate::{
+    action::{Action, Result},
+    InstallFiles,
+};
+
+/// Removes every file listed in the install manifest, along with the
+/// directories that only existed to hold them, and then the manifest itself
+///
+pub struct UninstallFiles {}
+
+impl UninstallFiles {
+    pub fn new() -> Box<Self> {
+        Box::new(Self {})
+    }
+
+    // install/include/[name]/... should disappear with the last header in it,
+    // but the bin, lib and include directories of the prefix stay
+    fn remove_empty_parents(path: &path::Path) {
+        for dir in path.ancestors().skip(1) {
+            let is_prefix_dir = dir
+                .file_name()
+                .is_some_and(|name| name == "bin" || name == "lib" || name == "include");
+
+            if is_prefix_dir || fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+impl Action for UninstallFiles {
+    fn execute(&mut self) -> Result<()> {
+        println!("=> Uninstalling...");
+
+        let manifest_path = InstallFiles::manifest_path();
+        let manifest = match fs::read_to_string(&manifest_path) {
+            Ok(val) => val,
+            Err(_) => {
+                return Err(format!(
+                    "Failed to read {}, has the project been installed?",
+                    manifest_path.to_str().unwrap()
+                )
+                .into());
+            }
+        };
+
+        for line in manifest.lines().filter(|line| !line.trim().is_empty()) {
+            let path = path::Path::new(line);
+
+            if !path.exists() {
+                println!("    -> {} was already removed", line);
+                continue;
+            }
+
+            if fs::remove_file(path).is_err() {
+                return Err(format!("Failed to remove {}", line).into());
+            }
+
+            println!("    -> removed {}", line);
+            Self::remove_empty_parents(path);
+        }
+
+        if fs::remove_file(&manifest_path).is_err() {
+            return Err(format!("Failed to remove {}", manifest_path.to_str().unwrap()).into());
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // removed files can't be brought back
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use std::{fs, path};
+
+use crate::action::{Action, Result};
+
+/// Writes data to a file, unlike [`CreateFile`](crate::CreateFile) it is not an
+/// error for the file to already exist, it just gets overwritten
+///
+/// Undoing restores the file that was overwritten, or removes the file if it
+/// didnt exist before
+///
+pub struct WriteFile {
+    path: path::PathBuf,
+    contents: Vec<u8>,
+    // Some once written, holding what the file held before if it existed
+    old_contents: Option<Option<Vec<u8>>>,
+}
+
+impl WriteFile {
+    pub fn new<P>(path: P, contents: &[u8]) -> Box<Self>
+    where
+        P: AsRef<path::Path>,
+    {
+        Box::new(Self {
+            path: path.as_ref().to_path_buf(),
+            contents: contents.to_vec(),
+            old_contents: None,
+        })
+    }
+}
+
+impl Action for WriteFile {
+    fn execute(&mut self) -> Result<()> {
+        let old_contents = fs::read(&self.path).ok();
+
+        if fs::write(&self.path, &self.contents).is_err() {
+            Err(format!("Failed to write {}", self.path.to_str().unwrap_or("file")).into())
+        } else {
+            self.old_contents = Some(old_contents);
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let res = match self.old_contents.take() {
+            None => return Ok(()),
+            Some(Some(contents)) => fs::write(&self.path, contents),
+            Some(None) => fs::remove_file(&self.path),
+        };
+
+        if res.is_err() {
+            Err(format!("Failed to restore {}", self.path.to_str().unwrap_or("file")).into())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Lang},
+    crash::CrashReport,
+    profile::Profile,
+    runner::Runner,
+    ActionChain, BuildProject, ReadConfig, RunTestCases,
+};
+
+pub struct TestProject {
+    action_chain: Box<ActionChain>,
+}
+
+impl TestProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Create Argument Retriever
+        let mut arg_retriever = Self::create_arg_retriever();
+        arg_retriever.load(args);
+
+        // Get Necessary Arguments
+        // an invalid profile is reported by the build action before it gets used
+        let profile = Profile::from_args(&arg_retriever).unwrap_or_else(|_| Profile::new(false));
+        let crash_report = CrashReport::from_args(&arg_retriever, &profile);
+        let runner = Runner::from_args(&arg_retriever);
+        let bless = arg_retriever.has_tag("--bless");
+        let filters = arg_retriever.get_untagged();
+
+        // Create Action Arguments
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+
+        // Create Action Chain
+        let mut action_chain = ActionChain::new();
+
+        action_chain
+            .add(BuildProject::new(args))
+            .add(ReadConfig::new(Rc::clone(&config)))
+            .add(RunTestCases::new(
+                Rc::clone(&config),
+                profile,
+                bless,
+                filters,
+                crash_report,
+                runner,
+            ));
+
+        Box::new(Self { action_chain })
+    }
+
+    fn create_arg_retriever() -> ArgRetriever {
+        let mut rules = BuildProject::arg_rules();
+        rules.extend(CrashReport::arg_rules());
+        rules.extend(Runner::arg_rules());
+        rules.push(ArgRule::new("--bless", 0));
+        ArgRetriever::new(&rules)
+    }
+}
+
+impl Action for TestProject {
+    fn execute(&mut self) -> Result<()> {
+        self.action_chain
+            .execute()
+            .map_err(|err| err.prepend("Failed to test project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain
+            .undo()
+            .map_err(|err| err.prepend("Failed to undo project test: "))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    action::{Action, Result},
+    config_file, ActionChain, HasFile, UninstallFiles,
+};
+
+pub struct UninstallProject {
+    action_chain: Box<ActionChain>,
+}
+
+impl UninstallProject {
+    pub fn new() -> Box<Self> {
+        let mut action_chain = ActionChain::new();
+        action_chain
+            .add(HasFile::new(config_file::path()))
+            .add(UninstallFiles::new());
+
+        Box::new(Self { action_chain })
+    }
+}
+
+impl Action for UninstallProject {
+    fn execute(&mut self) -> Result<()> {
+        self.action_chain
+            .execute()
+            .map_err(|err| err.prepend("Failed to uninstall project: "))
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        self.action_chain.undo()
+    }
+}
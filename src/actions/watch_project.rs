@@ -0,0 +1,117 @@
+use std::{cell::RefCell, path, rc::Rc};
+
+use crate::{
+    action::{Action, Result},
+    config::{Config, Lang},
+    config_file,
+    watcher::Watcher,
+    BuildProject, CheckProject, ReadConfig, RunProject, TestProject,
+};
+
+// cproj watch [build, run, test, check] [pipeline args] (default = build)
+
+enum WatchProjectState {
+    ValidArguments,
+    InvalidPipeline,
+}
+
+pub struct WatchProject {
+    // built once and executed again on every change, so the sub actions
+    // can hold on to what they worked out in the previous iteration
+    pipeline: Box<dyn Action>,
+    config: Rc<RefCell<Config>>,
+    read_config: Box<ReadConfig>,
+    state: WatchProjectState,
+}
+
+impl WatchProject {
+    pub fn new(args: &[&str]) -> Box<Self> {
+        // Get Necessary Arguments
+        let mut state = WatchProjectState::ValidArguments;
+
+        let (pipeline_name, pipeline_args) = match args.first() {
+            Some(arg) if !arg.starts_with('-') => (arg.to_lowercase(), &args[1..]),
+            _ => ("build".to_string(), args),
+        };
+
+        let pipeline: Box<dyn Action> = match pipeline_name.as_str() {
+            "build" => BuildProject::new(pipeline_args),
+            "run" => RunProject::new(pipeline_args),
+            "test" => TestProject::new(pipeline_args),
+            "check" => CheckProject::new(pipeline_args),
+            _ => {
+                state = WatchProjectState::InvalidPipeline;
+                BuildProject::new(pipeline_args)
+            }
+        };
+
+        // Create Action Arguments
+        let config = Rc::new(RefCell::new(Config::new("", Lang::C)));
+
+        Box::new(Self {
+            pipeline,
+            read_config: ReadConfig::new(Rc::clone(&config)),
+            config,
+            state,
+        })
+    }
+
+    fn run_pipeline(&mut self) {
+        if let Err(err) = self.pipeline.execute() {
+            eprintln!("Error: {}", err.get_msg());
+            if let Err(err) = self.pipeline.undo() {
+                eprintln!("Error: {}", err.get_msg());
+            }
+        }
+    }
+
+    /// the directories and files that trigger a rerun when they change
+    fn watched_paths(&mut self) -> (Vec<path::PathBuf>, Vec<path::PathBuf>) {
+        let mut dirs = vec![path::PathBuf::from("src")];
+
+        // a broken config still leaves src and the config itself to watch
+        if self.read_config.execute().is_ok() {
+            dirs.extend(self.config.borrow().include.iter().map(path::PathBuf::from));
+        }
+
+        // both formats are watched so that switching between them is noticed
+        let files = config_file::Format::ALL
+            .iter()
+            .map(|format| path::PathBuf::from(format.file_name()))
+            .collect();
+
+        (dirs, files)
+    }
+}
+
+impl Action for WatchProject {
+    fn execute(&mut self) -> Result<()> {
+        if let WatchProjectState::InvalidPipeline = self.state {
+            return Err("Failed to watch project: Invalid pipeline argument".into());
+        }
+
+        self.run_pipeline();
+
+        let (dirs, files) = self.watched_paths();
+        let mut watcher = Watcher::new(dirs, files);
+
+        loop {
+            if watcher.is_polling() {
+                println!("\n=> Watching for changes (polling)...");
+            } else {
+                println!("\n=> Watching for changes...");
+            }
+
+            watcher.wait();
+            self.run_pipeline();
+
+            let (dirs, files) = self.watched_paths();
+            watcher.set_paths(dirs, files);
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        // every iteration undoes its own failures
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use std::{collections::HashMap, hash::Hash};
 pub struct ArgRetriever {
     tagged: HashMap<ArgRule, Option<Vec<String>>>,
     untagged: Vec<String>,
+    trailing: Vec<String>,
 }
 
 impl ArgRetriever {
@@ -16,6 +17,7 @@ impl ArgRetriever {
         Self {
             tagged,
             untagged: vec![],
+            trailing: vec![],
         }
     }
 
@@ -24,10 +26,17 @@ impl ArgRetriever {
         self.tagged.iter_mut().for_each(|(_, value)| *value = None);
 
         self.untagged.clear();
+        self.trailing.clear();
 
         // load in args
         let mut i = 0;
         while i < args.len() {
+            // everything after -- is left alone so it can be passed on as is
+            if args[i] == "--" {
+                self.trailing = args[i + 1..].iter().map(|val| val.to_string()).collect();
+                break;
+            }
+
             // this will only ever contain 0 or 1 key
             let possible_keys: Vec<ArgRule> = self
                 .tagged
@@ -72,6 +81,11 @@ impl ArgRetriever {
     pub fn get_untagged(&self) -> Vec<String> {
         self.untagged.clone()
     }
+
+    /// the args that came after a `--`
+    pub fn get_trailing(&self) -> Vec<String> {
+        self.trailing.clone()
+    }
 }
 
 /// Weve implemented Hash and PartialEq the way we have since
@@ -1,12 +1,38 @@
-use serde::{Deserialize, Serialize};
-use std::fmt;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+};
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+use crate::glob;
+
+#[derive(Clone, Copy, Serialize)]
 pub enum Lang {
     C,
     Cpp,
 }
 
+impl Lang {
+    /// parses a language the way the --lang flag does, ignoring casing
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "c" => Some(Lang::C),
+            "cpp" | "c++" => Some(Lang::Cpp),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Lang {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Lang::from_name(&name).ok_or_else(|| de::Error::unknown_variant(&name, &["C", "Cpp"]))
+    }
+}
+
 impl fmt::Display for Lang {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -16,38 +42,302 @@ impl fmt::Display for Lang {
     }
 }
 
+/// What the project gets linked into
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Kind {
+    #[default]
+    Bin,
+    StaticLib,
+    SharedLib,
+}
+
+impl Kind {
+    pub fn is_lib(&self) -> bool {
+        *self != Self::Bin
+    }
+}
+
+/// Describes the archives made by cproj package
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Package {
+    #[serde(default = "Package::default_version")]
+    pub version: String,
+    /// the license file, LICENSE or COPYING are picked when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// the readme file, README is picked when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+}
+
+impl Package {
+    fn default_version() -> String {
+        "0.1.0".to_string()
+    }
+}
+
+impl Default for Package {
+    fn default() -> Self {
+        Self {
+            version: Self::default_version(),
+            license: None,
+            readme: None,
+        }
+    }
+}
+
+/// The settings of one build mode
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileSettings {
+    /// the flags passed to the compile stage
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// a command the binary is run through by cproj run and cproj test
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runner: Option<String>,
+}
+
+/// The settings of each build mode
 #[derive(Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Profiles {
+    #[serde(default = "Profiles::default_debug")]
+    pub debug: ProfileSettings,
+    #[serde(default = "Profiles::default_release")]
+    pub release: ProfileSettings,
+}
+
+impl Profiles {
+    pub fn default_debug() -> ProfileSettings {
+        ProfileSettings {
+            flags: vec!["-Wall".to_string(), "-Wextra".to_string(), "-g".to_string()],
+            runner: None,
+        }
+    }
+
+    pub fn default_release() -> ProfileSettings {
+        ProfileSettings {
+            flags: vec![
+                "-Wall".to_string(),
+                "-Wextra".to_string(),
+                "-O2".to_string(),
+            ],
+            runner: None,
+        }
+    }
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            debug: Self::default_debug(),
+            release: Self::default_release(),
+        }
+    }
+}
+
+/// Changes the compile flags of the files that match a glob pattern, see
+/// [`glob::matches()`](crate::glob::matches)
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Override {
+    /// the pattern the files match, relative to the project root
+    pub files: String,
+    /// flags added after the flags of the profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+    /// flags taken out of the flags of the profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_flags: Vec<String>,
+    /// defines added with -D, as NAME or NAME=VALUE
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defines: Vec<String>,
+    /// names whose -D flags are taken out, whatever their value
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_defines: Vec<String>,
+}
+
+impl Override {
+    pub fn matches(&self, path: &Path) -> bool {
+        glob::matches(&self.files, &path.to_string_lossy())
+    }
+
+    /// removes and then adds the flags and defines of the override
+    pub fn apply(&self, flags: &mut Vec<String>) {
+        flags.retain(|flag| {
+            let define = flag
+                .strip_prefix("-D")
+                .map(|define| define.split_once('=').map_or(define, |(name, _)| name));
+
+            !self.remove_flags.contains(flag)
+                && !define.is_some_and(|name| self.remove_defines.iter().any(|val| val == name))
+        });
+
+        flags.extend(self.flags.iter().cloned());
+        flags.extend(self.defines.iter().map(|define| format!("-D{}", define)));
+    }
+}
+
+/// The platform a [`Conditional`] section applies to, every condition that is
+/// given has to hold. A condition starting with `!` holds when the value is
+/// anything else, see [`platform::Platform`](crate::platform::Platform)
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct When {
+    /// such as linux, macos or windows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    /// such as x86_64 or aarch64
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    /// a glob pattern matched against the target triple of the compiler,
+    /// such as `aarch64-*-linux-gnu`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// Settings that are added to the config when the platform matches, see
+/// [`effective::resolve()`](crate::effective::resolve)
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Conditional {
+    pub when: When,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lib: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_flags: Vec<String>,
+    /// added to the flags of every profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+    /// added to the flags of every profile with -D, as NAME or NAME=VALUE
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<Override>,
+}
+
+/// The project config, only `name` is required
+///
+/// Older layouts of the config are brought up to date by
+/// [`config_file::migrate()`](crate::config_file::migrate) before they get
+/// here
+///
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
+    /// the layout the config is written in, see [`Config::VERSION`]
+    #[serde(default = "Config::current_version")]
+    pub version: u64,
     pub name: String,
+    /// gcc or g++ depending on `lang` when missing, see [`Config::fill_defaults()`].
+    /// New projects leave it out so that the user config or CC can pick it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub cc: String,
+    #[serde(default = "Config::default_include")]
     pub include: Vec<String>,
+    #[serde(default = "Config::default_lib")]
     pub lib: Vec<String>,
+    #[serde(default)]
     pub link_flags: Vec<String>,
-    pub debug_flags: Vec<String>,
-    pub release_flags: Vec<String>,
+    #[serde(default)]
+    pub profiles: Profiles,
+    /// applied in order to each file they match, so later overrides can undo
+    /// what earlier ones did
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<Override>,
+    /// glob patterns of source files that dont get compiled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// sections that only apply to some platforms
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditional: Vec<Conditional>,
+    #[serde(default = "Config::default_lang")]
     pub lang: Lang,
+    #[serde(default)]
+    pub kind: Kind,
+    /// the debugger used by cproj debug, gdb or lldb are picked when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debugger: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<Package>,
 }
 
 impl Config {
-    pub fn new(name: &str, lang: Lang) -> Self {
-        let cc = match lang {
-            Lang::C => "gcc",
-            Lang::Cpp => "g++",
-        };
+    /// the version of the current layout, bumped whenever fields move so that
+    /// older configs can be migrated
+    pub const VERSION: u64 = 2;
 
+    pub fn new(name: &str, lang: Lang) -> Self {
         Self {
+            version: Self::VERSION,
             name: name.to_string(),
-            cc: cc.to_string(),
-            include: vec!["include".to_string()],
-            lib: vec!["lib".to_string()],
+            cc: String::new(),
+            include: Self::default_include(),
+            lib: Self::default_lib(),
             link_flags: vec![],
-            debug_flags: vec!["-Wall".to_string(), "-Wextra".to_string()],
-            release_flags: vec![
-                "-Wall".to_string(),
-                "-Wextra".to_string(),
-                "-O2".to_string(),
-            ],
+            profiles: Profiles::default(),
+            overrides: vec![],
+            exclude: vec![],
+            conditional: vec![],
             lang,
+            kind: Kind::Bin,
+            debugger: None,
+            package: None,
+        }
+    }
+
+    /// fills in the defaults that depend on other fields, needs to be called
+    /// on every config that was deserialized
+    pub fn fill_defaults(&mut self) {
+        if self.cc.is_empty() {
+            self.cc = Self::default_cc(self.lang);
+        }
+    }
+
+    /// checks if a source file is left out of the build by `exclude`
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let path = path.to_string_lossy();
+        self.exclude
+            .iter()
+            .any(|pattern| glob::matches(pattern, &path))
+    }
+
+    fn default_cc(lang: Lang) -> String {
+        match lang {
+            Lang::C => "gcc".to_string(),
+            Lang::Cpp => "g++".to_string(),
         }
     }
+
+    fn default_include() -> Vec<String> {
+        vec!["include".to_string()]
+    }
+
+    fn default_lib() -> Vec<String> {
+        vec!["lib".to_string()]
+    }
+
+    fn current_version() -> u64 {
+        Self::VERSION
+    }
+
+    fn default_lang() -> Lang {
+        Lang::C
+    }
+}
+
+/// The directory holding the user's own cproj files, such as templates
+/// (`$XDG_CONFIG_HOME/cproj` or `~/.config/cproj`)
+pub fn user_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|val| !val.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("cproj"))
 }
@@ -0,0 +1,425 @@
+use serde_json::Value;
+
+use crate::{
+    action::Result,
+    config_file::{self, Format},
+    effective,
+};
+
+/// A change to one field of the project config, fields of sections are named
+/// with dots such as `profiles.debug.flags`
+pub enum Edit {
+    /// replaces the value, lists take every value that is given
+    Set(Vec<String>),
+    /// adds values to a list, skipping the ones it already holds
+    Add(Vec<String>),
+    /// removes values from a list, or the field itself when no values are
+    /// given so that it goes back to its default
+    Remove(Vec<String>),
+}
+
+impl Edit {
+    pub fn from_name(name: &str, values: Vec<String>) -> Option<Self> {
+        match name {
+            "set" => Some(Self::Set(values)),
+            "add" => Some(Self::Add(values)),
+            "remove" => Some(Self::Remove(values)),
+            _ => None,
+        }
+    }
+}
+
+/// The value of a field of the project config as it is written, with the
+/// defaults of the fields it leaves out
+pub fn get(format: Format, contents: &str, field: &str) -> Result<Value> {
+    let config = serde_json::to_value(format.parse(contents)?).unwrap();
+
+    effective::lookup(&config, field)
+        .cloned()
+        .ok_or_else(|| format!("{} isnt set", field).into())
+}
+
+/// Applies an edit to the contents of a config, changing as little of the
+/// file as it can, and gives the new contents
+/// # Error
+/// If the edited config isnt valid, with the same message that reading it
+/// would give
+///
+pub fn apply(format: Format, contents: &str, field: &str, edit: &Edit) -> Result<String> {
+    let fields = format.parse_value(contents)?;
+
+    if config_file::migrate_layout(&mut fields.clone())?.is_some() {
+        return Err("the config is in an older layout, run cproj migrate first".into());
+    }
+
+    // the current value, or its default, tells what kind of value the field
+    // takes
+    let config = serde_json::to_value(config_file::from_value(fields.clone())?).unwrap();
+    let current = effective::lookup(&config, field).cloned();
+
+    let value = match edit {
+        Edit::Set(values) => Some(parse_values(field, current.as_ref(), values)?),
+        Edit::Add(values) => {
+            let mut items = list(field, current.clone())?;
+            for value in values.iter().map(|value| Value::from(value.as_str())) {
+                if !items.contains(&value) {
+                    items.push(value);
+                }
+            }
+            Some(Value::Array(items))
+        }
+        Edit::Remove(values) if values.is_empty() => {
+            if effective::lookup(&fields, field).is_none() {
+                return Err(format!("{} isnt set", field).into());
+            }
+            None
+        }
+        Edit::Remove(values) => {
+            let mut items = list(field, current.clone())?;
+            for value in values {
+                let Some(i) = items.iter().position(|item| item.as_str() == Some(value)) else {
+                    return Err(format!("{} doesnt hold {}", field, value).into());
+                };
+                items.remove(i);
+            }
+            Some(Value::Array(items))
+        }
+    };
+    // toml has no null, and for optional fields it means the same thing
+    let value = value.filter(|value| !value.is_null());
+
+    let mut edited = fields.clone();
+    set_field(&mut edited, field, value.clone())?;
+    let mut is_valid = config_file::from_value(edited.clone());
+
+    // a field without a value could also be a list that is empty, which
+    // takes a single value just as well
+    let value = match (&is_valid, &current, value) {
+        (Err(_), None, Some(Value::String(single))) => {
+            let list = Some(Value::Array(vec![Value::String(single)]));
+            edited = fields.clone();
+            set_field(&mut edited, field, list.clone())?;
+            is_valid = config_file::from_value(edited.clone()).or(is_valid);
+            list
+        }
+        (_, _, value) => value,
+    };
+    is_valid.map_err(|err| err.prepend("the edited config is invalid: "))?;
+    let fields = edited;
+
+    match format {
+        Format::Json => Ok(write_json(&fields, contents)),
+        Format::Toml => edit_toml(contents, field, value),
+    }
+}
+
+// lists that are empty arent written out, so a missing value is taken as an
+// empty list
+fn list(field: &str, current: Option<Value>) -> Result<Vec<Value>> {
+    match current {
+        Some(Value::Array(items)) => Ok(items),
+        None => Ok(vec![]),
+        _ => Err(format!("{} isnt a list", field).into()),
+    }
+}
+
+// values are taken as the kind of value the field already holds, so that
+// quoting isnt needed for strings, anything else can be given as json
+fn parse_values(field: &str, current: Option<&Value>, values: &[String]) -> Result<Value> {
+    let parse_json = |value: &str| {
+        serde_json::from_str::<Value>(value)
+            .map_err(|err| format!("{} isnt valid json: {}", value, err).into())
+    };
+
+    if let [value] = values {
+        if value.starts_with('[') || value.starts_with('{') {
+            return parse_json(value);
+        }
+    }
+
+    match (current, values) {
+        (_, []) => Err(format!("no value was given for {}", field).into()),
+        (Some(Value::Array(_)), values) => Ok(values.iter().map(|val| val.as_str()).collect()),
+        (Some(Value::Object(_)), _) => {
+            Err(format!("{} is a section, set its fields one at a time", field).into())
+        }
+        (Some(Value::Number(_) | Value::Bool(_)), [value]) => parse_json(value),
+        (Some(_), [value]) => Ok(Value::from(value.as_str())),
+        (Some(_), _) => Err(format!("{} takes a single value", field).into()),
+        // without a value to go by, a single value is a string until it turns
+        // out to be a list
+        (None, [value]) => Ok(Value::from(value.as_str())),
+        (None, values) => Ok(values.iter().map(|val| val.as_str()).collect()),
+    }
+}
+
+// sets or removes a field, adding the sections it is in when needed
+fn set_field(fields: &mut Value, field: &str, value: Option<Value>) -> Result<()> {
+    let (parents, name) = match field.rsplit_once('.') {
+        Some((parents, name)) => (parents.split('.').collect(), name),
+        None => (vec![], field),
+    };
+
+    let mut table = fields;
+    for parent in parents {
+        let Some(section) = table.as_object_mut() else {
+            return Err(format!("{} is inside a value that isnt a section", field).into());
+        };
+        table = section
+            .entry(parent)
+            .or_insert_with(|| Value::Object(Default::default()));
+    }
+
+    let Some(table) = table.as_object_mut() else {
+        return Err(format!("{} is inside a value that isnt a section", field).into());
+    };
+    match value {
+        Some(value) => table.insert(name.to_string(), value),
+        None => table.shift_remove(name),
+    };
+
+    Ok(())
+}
+
+// json has no comments, so keeping the order of the fields and the
+// indentation of the file is as close as it gets
+fn write_json(fields: &Value, original: &str) -> String {
+    let indent = original
+        .lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ");
+
+    let mut output = vec![];
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+    serde::Serialize::serialize(fields, &mut serializer).unwrap();
+
+    let mut output = String::from_utf8(output).unwrap();
+    if original.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+// edits the document in place so that comments and the layout of the other
+// fields are kept
+fn edit_toml(contents: &str, field: &str, value: Option<Value>) -> Result<String> {
+    let mut document: toml_edit::DocumentMut = contents
+        .parse()
+        .map_err(|err: toml_edit::TomlError| err.message().to_string())?;
+
+    let mut names: Vec<_> = field.split('.').collect();
+    let name = names.pop().unwrap();
+
+    let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+    for parent in names {
+        let mut section = toml_edit::Table::new();
+        // only gets a header once it holds a field
+        section.set_implicit(true);
+
+        table = table
+            .entry(parent)
+            .or_insert(toml_edit::Item::Table(section))
+            .as_table_like_mut()
+            .ok_or_else(|| format!("{} is inside a value that isnt a section", field))?;
+    }
+
+    match value {
+        None => {
+            table.remove(name);
+        }
+        Some(value) => {
+            let mut value = to_toml(&value);
+            // keeps the comments around the old value
+            if let Some(old) = table.get_mut(name).and_then(|item| item.as_value_mut()) {
+                *value.decor_mut() = old.decor().clone();
+                *old = value;
+            } else {
+                table.insert(name, toml_edit::Item::Value(value));
+            }
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+/// Writes new fields over the contents of a config, only touching the fields
+/// that changed so that the comments and layout of the others are kept.
+/// `moved` names fields that moved, along with where to, so that their
+/// comments move with them
+///
+pub fn rewrite(
+    format: Format,
+    contents: &str,
+    fields: &Value,
+    moved: &[(&str, &str)],
+) -> Result<String> {
+    match format {
+        Format::Json => Ok(write_json(fields, contents)),
+        Format::Toml => {
+            let original = format.parse_value(contents)?;
+            let mut document: toml_edit::DocumentMut = contents
+                .parse()
+                .map_err(|err: toml_edit::TomlError| err.message().to_string())?;
+            let original_document = document.clone();
+
+            sync_table(document.as_table_mut(), &original, fields);
+            for (from, to) in moved {
+                carry_comments(&original_document, &mut document, from, to);
+            }
+            Ok(document.to_string())
+        }
+    }
+}
+
+// gives a field that moved the comments it had where it was
+fn carry_comments(
+    original: &toml_edit::DocumentMut,
+    document: &mut toml_edit::DocumentMut,
+    from: &str,
+    to: &str,
+) {
+    let Some((old_key, old_item)) = original.as_table().get_key_value(from) else {
+        return;
+    };
+
+    let mut names: Vec<_> = to.split('.').collect();
+    let name = names.pop().unwrap();
+
+    let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+    for parent in names {
+        let Some(section) = table
+            .get_mut(parent)
+            .and_then(|item| item.as_table_like_mut())
+        else {
+            return;
+        };
+        table = section;
+    }
+
+    if let Some(mut key) = table.key_mut(name) {
+        *key.leaf_decor_mut() = old_key.leaf_decor().clone();
+    }
+    let value = table.get_mut(name).and_then(|item| item.as_value_mut());
+    if let (Some(value), Some(old_value)) = (value, old_item.as_value()) {
+        *value.decor_mut() = old_value.decor().clone();
+    }
+}
+
+// brings a table in line with the fields it should hold, which also give the
+// order of its values
+fn sync_table(table: &mut toml_edit::Table, original: &Value, fields: &Value) {
+    let Some(fields) = fields.as_object() else {
+        return;
+    };
+    let first = table.iter().next().map(|(name, _)| name.to_string());
+
+    table.retain(|name, _| fields.contains_key(name));
+
+    for (name, value) in fields {
+        let old_value = effective::lookup(original, name);
+        if old_value == Some(value) {
+            continue;
+        }
+
+        match (table.get_mut(name), value) {
+            (Some(toml_edit::Item::Table(section)), Value::Object(_)) => {
+                sync_table(section, old_value.unwrap_or(&Value::Null), value);
+            }
+            // keeps the comments around the old value
+            (Some(toml_edit::Item::Value(old)), value) if !is_section(value) => {
+                let mut value = to_toml(value);
+                *value.decor_mut() = old.decor().clone();
+                *old = value;
+            }
+            (_, value) => {
+                table.insert(name, to_toml_item(value));
+            }
+        }
+    }
+
+    let order: Vec<_> = fields.keys().collect();
+    let position = |name: &toml_edit::Key| order.iter().position(|field| *field == name.get());
+    table.sort_values_by(|a, _, b, _| position(a).cmp(&position(b)));
+
+    // comments at the top of the table stay at the top when a new field goes
+    // first
+    let new_first = table.iter().next().map(|(name, _)| name.to_string());
+    if let (Some(first), Some(new_first)) = (first, new_first) {
+        let prefix = table
+            .get_key_value(&first)
+            .filter(|_| first != new_first)
+            .and_then(|(key, _)| key.leaf_decor().prefix().cloned());
+
+        if let Some(prefix) = prefix {
+            table
+                .key_mut(&first)
+                .unwrap()
+                .leaf_decor_mut()
+                .set_prefix("");
+            table
+                .key_mut(&new_first)
+                .unwrap()
+                .leaf_decor_mut()
+                .set_prefix(prefix);
+        }
+    }
+}
+
+// tables and lists of tables are written as sections
+fn is_section(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => true,
+        Value::Array(values) => !values.is_empty() && values.iter().all(Value::is_object),
+        _ => false,
+    }
+}
+
+fn to_toml_item(value: &Value) -> toml_edit::Item {
+    match value {
+        Value::Object(fields) => {
+            let mut table = toml_edit::Table::new();
+            // only gets a header if it holds more than sections
+            table.set_implicit(true);
+            for (name, value) in fields {
+                table.insert(name, to_toml_item(value));
+            }
+            toml_edit::Item::Table(table)
+        }
+        Value::Array(values) if is_section(value) => {
+            let mut tables = toml_edit::ArrayOfTables::new();
+            for value in values {
+                if let toml_edit::Item::Table(table) = to_toml_item(value) {
+                    tables.push(table);
+                }
+            }
+            toml_edit::Item::ArrayOfTables(tables)
+        }
+        value => toml_edit::Item::Value(to_toml(value)),
+    }
+}
+
+fn to_toml(value: &Value) -> toml_edit::Value {
+    match value {
+        Value::Bool(val) => (*val).into(),
+        Value::Number(val) => match val.as_i64() {
+            Some(val) => val.into(),
+            None => val.as_f64().unwrap_or_default().into(),
+        },
+        Value::String(val) => val.as_str().into(),
+        Value::Array(values) => values
+            .iter()
+            .map(to_toml)
+            .collect::<toml_edit::Array>()
+            .into(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), to_toml(value)))
+            .collect::<toml_edit::InlineTable>()
+            .into(),
+        // nulls are removed before getting here
+        Value::Null => "".into(),
+    }
+}
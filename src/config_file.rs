@@ -0,0 +1,393 @@
+use serde::de::DeserializeOwned;
+use std::path::{self, PathBuf};
+
+use crate::{
+    action::Result,
+    config::{Config, Profiles},
+};
+
+/// A format the project config can be written in, each with its own file name
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    pub const ALL: [Self; 2] = [Self::Json, Self::Toml];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Json => "cproj.json",
+            Self::Toml => "cproj.toml",
+        }
+    }
+
+    pub fn serialize(&self, config: &Config) -> String {
+        match self {
+            Self::Json => serde_json::to_string_pretty(config).unwrap(),
+            Self::Toml => toml::to_string_pretty(config).unwrap(),
+        }
+    }
+
+    /// Parses a config and fills in its defaults, configs in an older layout
+    /// are migrated first
+    /// # Error
+    /// The error names the line, the column and the field that are wrong, and
+    /// suggests the closest valid name for unknown fields and values
+    ///
+    pub fn parse(&self, contents: &str) -> Result<Config> {
+        // syntax errors are reported with their position by parse_current
+        let Ok(mut value) = self.parse_value(contents) else {
+            return self.parse_current(contents);
+        };
+
+        match migrate_layout(&mut value)? {
+            None => {
+                // an older version without any older fields reads the same
+                let mut config = self.parse_current(contents)?;
+                config.version = Config::VERSION;
+                Ok(config)
+            }
+            // the positions in the file dont match the migrated config
+            Some(version) => from_value(value)
+                .map_err(|err| err.prepend(&format!("after migrating from version {}: ", version))),
+        }
+    }
+
+    /// the config as a generic value, which keeps the order of its fields
+    pub fn parse_value(&self, contents: &str) -> Result<serde_json::Value> {
+        match self {
+            Self::Json => serde_json::from_str(contents).map_err(|err| err.to_string().into()),
+            Self::Toml => toml::from_str::<toml::Table>(contents)
+                .map_err(|err| err.message().to_string().into())
+                .and_then(|table| {
+                    serde_json::to_value(table).map_err(|err| err.to_string().into())
+                }),
+        }
+    }
+
+    /// the fields a config sets as it is written, after migrating it, or
+    /// Null if it cant be parsed. The version migrating fills in isnt one of
+    /// them
+    pub fn written_fields(&self, contents: &str) -> serde_json::Value {
+        let mut value = self.parse_value(contents).unwrap_or_default();
+        let has_version = value.get("version").is_some();
+
+        match migrate(&mut value) {
+            Ok(_) => {
+                if !has_version {
+                    value.as_object_mut().unwrap().shift_remove("version");
+                }
+                value
+            }
+            Err(_) => serde_json::Value::Null,
+        }
+    }
+
+    // parses a config that is in the current layout
+    fn parse_current(&self, contents: &str) -> Result<Config> {
+        let mut config: Config = self.deserialize(contents)?;
+        config.fill_defaults();
+        Ok(config)
+    }
+
+    /// Deserializes a file in this format with the same detailed errors as
+    /// [`Format::parse()`], for files other than the project config
+    pub fn deserialize<T: DeserializeOwned>(&self, contents: &str) -> Result<T> {
+        let parsed = match self {
+            Self::Json => {
+                // the position is reported separately from the message
+                let describe = |field: String, err: serde_json::Error| {
+                    let msg = err.to_string();
+                    let msg = match msg.rfind(" at line ") {
+                        Some(i) => msg[..i].to_string(),
+                        None => msg,
+                    };
+
+                    (field, msg, Some((err.line(), err.column())))
+                };
+
+                let mut deserializer = serde_json::Deserializer::from_str(contents);
+                match serde_path_to_error::deserialize(&mut deserializer) {
+                    Ok(config) => deserializer
+                        .end()
+                        .map(|_| config)
+                        .map_err(|err| describe(".".to_string(), err)),
+                    Err(err) => Err(describe(err.path().to_string(), err.into_inner())),
+                }
+            }
+            Self::Toml => serde_path_to_error::deserialize(toml::Deserializer::new(contents))
+                .map_err(|err| {
+                    let field = err.path().to_string();
+                    let err = err.into_inner();
+                    let position = err.span().map(|span| line_and_column(contents, span.start));
+
+                    (field, err.message().to_string(), position)
+                }),
+        };
+
+        parsed.map_err(|(field, msg, position)| describe_error(&field, &msg, position).into())
+    }
+}
+
+/// Parses a config from a generic value and fills in its defaults, see
+/// [`Format::parse_value()`]
+pub fn from_value(value: serde_json::Value) -> Result<Config> {
+    match serde_path_to_error::deserialize::<_, Config>(value) {
+        Ok(mut config) => {
+            config.fill_defaults();
+            Ok(config)
+        }
+        Err(err) => {
+            let field = err.path().to_string();
+            Err(describe_error(&field, &err.into_inner().to_string(), None).into())
+        }
+    }
+}
+
+/// Brings a config in an older layout up to the current one, see
+/// [`Config::VERSION`]. Gives the version it was migrated from, or None if it
+/// was already up to date
+///
+/// Configs without a version are from before versions were added, which
+/// makes them version 1
+///
+pub fn migrate(value: &mut serde_json::Value) -> Result<Option<u64>> {
+    let Some(fields) = value.as_object_mut() else {
+        return Err("the config has to be a table of fields".into());
+    };
+
+    let version = match fields.get("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .ok_or("version: expected a positive integer")?,
+    };
+
+    if version > Config::VERSION {
+        return Err(format!(
+            "the config is for version {} but this cproj only knows up to version {}, update cproj",
+            version,
+            Config::VERSION
+        )
+        .into());
+    }
+    if version == Config::VERSION {
+        return Ok(None);
+    }
+
+    if version < 2 {
+        migrate_to_profiles(fields);
+    }
+
+    // the version goes first, like in new configs
+    let mut migrated = serde_json::Map::new();
+    migrated.insert("version".to_string(), Config::VERSION.into());
+    migrated.extend(
+        std::mem::take(fields)
+            .into_iter()
+            .filter(|(key, _)| key != "version"),
+    );
+    *fields = migrated;
+
+    Ok(Some(version))
+}
+
+/// Like [`migrate()`], but only gives the version it was migrated from when
+/// migrating changed more than the version. Configs without a version that
+/// dont use any older fields are already laid out like the current version
+///
+pub fn migrate_layout(value: &mut serde_json::Value) -> Result<Option<u64>> {
+    let mut original = value.clone();
+    let Some(version) = migrate(value)? else {
+        return Ok(None);
+    };
+
+    let mut migrated = value.clone();
+    for value in [&mut original, &mut migrated] {
+        value.as_object_mut().unwrap().shift_remove("version");
+    }
+
+    Ok((original != migrated).then_some(version))
+}
+
+/// the fields that migrating moves, along with where they end up
+pub const MOVED_FIELDS: [(&str, &str); 4] = [
+    ("debug_flags", "profiles.debug.flags"),
+    ("debug_runner", "profiles.debug.runner"),
+    ("release_flags", "profiles.release.flags"),
+    ("release_runner", "profiles.release.runner"),
+];
+
+// version 2 moved debug_flags, release_flags, debug_runner and release_runner
+// into profiles.debug and profiles.release
+fn migrate_to_profiles(fields: &mut serde_json::Map<String, serde_json::Value>) {
+    let is_moved = |key: &str| MOVED_FIELDS.iter().any(|(moved, _)| *moved == key);
+    if !fields.keys().any(|key| is_moved(key)) {
+        return;
+    }
+
+    let mut profiles = serde_json::Map::new();
+    for (name, default) in [
+        ("debug", Profiles::default_debug()),
+        ("release", Profiles::default_release()),
+    ] {
+        let flags = fields.get(&format!("{}_flags", name)).cloned();
+        let runner = fields.get(&format!("{}_runner", name)).cloned();
+        if flags.is_none() && runner.is_none() {
+            continue;
+        }
+
+        // a profile without flags has none, so the old default is kept
+        let mut profile = serde_json::Map::new();
+        profile.insert(
+            "flags".to_string(),
+            flags.unwrap_or_else(|| default.flags.into()),
+        );
+        if let Some(runner) = runner {
+            profile.insert("runner".to_string(), runner);
+        }
+
+        profiles.insert(name.to_string(), profile.into());
+    }
+
+    // profiles takes the place of the first field it replaces
+    let mut migrated = serde_json::Map::new();
+    let mut profiles = Some(profiles);
+    for (key, value) in std::mem::take(fields) {
+        if is_moved(&key) {
+            if let Some(profiles) = profiles.take() {
+                migrated.insert("profiles".to_string(), profiles.into());
+            }
+        } else {
+            migrated.insert(key, value);
+        }
+    }
+    *fields = migrated;
+}
+
+// "line 3, column 5: field: message", suggesting the closest name for unknown
+// fields and values
+fn describe_error(field: &str, msg: &str, position: Option<(usize, usize)>) -> String {
+    let mut error = String::new();
+    if let Some((line, column)) = position {
+        error += &format!("line {}, column {}: ", line, column);
+    }
+
+    // syntax errors arent inside of any field, and the path of an
+    // unknown field already ends with its name
+    let is_unknown_field = msg.starts_with("unknown field");
+    if field != "." && !field.contains('?') && !is_unknown_field {
+        error += &format!("{}: ", field);
+    }
+
+    match suggest(msg) {
+        Some((_, suggestion)) if is_unknown_field => {
+            error += &format!("unknown field `{}`, did you mean `{}`?", field, suggestion)
+        }
+        Some((unknown, suggestion)) => {
+            error += &format!(
+                "unknown value `{}`, did you mean `{}`?",
+                unknown, suggestion
+            )
+        }
+        None if is_unknown_field => match msg.split_once(", ") {
+            Some((_, expected)) => error += &format!("unknown field `{}`, {}", field, expected),
+            None => error += &format!("unknown field `{}`", field),
+        },
+        None => error += msg,
+    }
+
+    error
+}
+
+// 1 based line and column of a byte offset
+fn line_and_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+// serde reports unknown names as "unknown field `x`, expected one of `a`, `b`",
+// this picks the expected name closest to x if it is close enough to be a typo
+fn suggest(msg: &str) -> Option<(String, String)> {
+    if !msg.starts_with("unknown field") && !msg.starts_with("unknown variant") {
+        return None;
+    }
+
+    let mut names = msg.split('`').skip(1).step_by(2);
+    let unknown = names.next()?;
+
+    names
+        .map(|name| {
+            (
+                edit_distance(&unknown.to_lowercase(), &name.to_lowercase()),
+                name,
+            )
+        })
+        .filter(|(distance, name)| *distance <= (name.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| (unknown.to_string(), name.to_string()))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Finds the config of the project in `dir`
+/// # Error
+/// If there is no config, or if there is both a cproj.json and a cproj.toml,
+/// since it wouldnt be clear which one is used
+///
+pub fn locate(dir: &path::Path) -> Result<(PathBuf, Format)> {
+    let found: Vec<_> = Format::ALL
+        .into_iter()
+        .map(|format| (dir.join(format.file_name()), format))
+        .filter(|(path, _)| path.is_file())
+        .collect();
+
+    match found.len() {
+        0 => Err("no cproj.json or cproj.toml was found".into()),
+        1 => Ok(found.into_iter().next().unwrap()),
+        _ => Err("both cproj.json and cproj.toml were found, remove one of them".into()),
+    }
+}
+
+/// the config file of the project in the current directory, or cproj.json if
+/// there isnt one so that errors still name a config file
+pub fn path() -> PathBuf {
+    locate(path::Path::new("."))
+        .map(|(path, _)| path.strip_prefix(".").unwrap().to_path_buf())
+        .unwrap_or_else(|_| PathBuf::from(Format::Json.file_name()))
+}
+
+/// checks if a directory already holds a config in any format
+pub fn exists(dir: &path::Path) -> bool {
+    Format::ALL
+        .iter()
+        .any(|format| dir.join(format.file_name()).exists())
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+
+/// Execution counts of a single source file
+#[derive(Default)]
+pub struct FileCoverage {
+    /// line number -> how many times it ran, only holds executable lines
+    pub lines: BTreeMap<u32, u64>,
+    /// line number -> how many times each branch on that line was taken,
+    /// None if the branch was never reached
+    pub branches: BTreeMap<u32, Vec<Option<u64>>>,
+}
+
+impl FileCoverage {
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|count| **count > 0).count()
+    }
+
+    pub fn branch_count(&self) -> usize {
+        self.branches.values().map(|val| val.len()).sum()
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.branches
+            .values()
+            .flatten()
+            .filter(|taken| taken.is_some_and(|val| val > 0))
+            .count()
+    }
+
+    // the same file (usually a header) can show up in multiple reports, so its
+    // counts get added together
+    fn merge(&mut self, other: FileCoverage) {
+        for (line, count) in other.lines {
+            *self.lines.entry(line).or_insert(0) += count;
+        }
+
+        for (line, branches) in other.branches {
+            let existing = self.branches.entry(line).or_default();
+
+            for (index, taken) in branches.into_iter().enumerate() {
+                match existing.get_mut(index) {
+                    Some(val) => {
+                        *val = match (*val, taken) {
+                            (None, None) => None,
+                            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+                        }
+                    }
+                    None => existing.push(taken),
+                }
+            }
+        }
+    }
+}
+
+pub fn percent(hit: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        hit as f64 / total as f64 * 100.0
+    }
+}
+
+/// Parses the text output of `gcov -b -c -t` (or `llvm-cov gcov -b -c -t`) and
+/// adds it to `report`, which is keyed by the source path gcov reports
+///
+pub fn parse_gcov(output: &str, report: &mut HashMap<String, FileCoverage>) {
+    let mut source: Option<String> = None;
+    let mut file = FileCoverage::default();
+    let mut last_line = 0;
+    // set while looking at lines gcov repeats for template instantiations
+    let mut skipping = false;
+
+    for text in output.lines() {
+        if let Some(branch) = text.strip_prefix("branch") {
+            if skipping || source.is_none() {
+                continue;
+            }
+
+            let taken = branch
+                .split_once("taken")
+                .and_then(|(_, rest)| rest.split_whitespace().next())
+                .and_then(|count| count.parse().ok());
+
+            file.branches.entry(last_line).or_default().push(taken);
+            continue;
+        }
+
+        let mut fields = text.splitn(3, ':');
+        let (count, line, code) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(count), Some(line), Some(code)) => (count.trim(), line.trim(), code),
+            _ => continue,
+        };
+
+        let line: u32 = match line.parse() {
+            Ok(val) => val,
+            Err(_) => continue,
+        };
+
+        if line == 0 {
+            if let Some(path) = code.strip_prefix("Source:") {
+                if let Some(prev) = source.take() {
+                    report.entry(prev).or_default().merge(file);
+                }
+
+                source = Some(path.to_string());
+                file = FileCoverage::default();
+                last_line = 0;
+                skipping = false;
+            }
+            continue;
+        }
+
+        skipping = line <= last_line;
+        if skipping {
+            continue;
+        }
+        last_line = line;
+
+        let count = match count.trim_end_matches('*') {
+            "-" => continue,
+            "#####" | "=====" => 0,
+            val => val.parse().unwrap_or(0),
+        };
+
+        file.lines.insert(line, count);
+    }
+
+    if let Some(prev) = source {
+        report.entry(prev).or_default().merge(file);
+    }
+}
+
+/// Writes the report in the lcov tracefile format
+pub fn to_lcov(report: &BTreeMap<String, FileCoverage>) -> String {
+    let mut output = String::new();
+
+    for (path, file) in report {
+        writeln!(output, "TN:").unwrap();
+        writeln!(output, "SF:{}", path).unwrap();
+
+        for (line, branches) in &file.branches {
+            for (index, taken) in branches.iter().enumerate() {
+                let taken = taken.map_or("-".to_string(), |val| val.to_string());
+                writeln!(output, "BRDA:{},0,{},{}", line, index, taken).unwrap();
+            }
+        }
+        writeln!(output, "BRF:{}", file.branch_count()).unwrap();
+        writeln!(output, "BRH:{}", file.branches_hit()).unwrap();
+
+        for (line, count) in &file.lines {
+            writeln!(output, "DA:{},{}", line, count).unwrap();
+        }
+        writeln!(output, "LF:{}", file.lines.len()).unwrap();
+        writeln!(output, "LH:{}", file.lines_hit()).unwrap();
+
+        writeln!(output, "end_of_record").unwrap();
+    }
+
+    output
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif}\
+table{border-collapse:collapse}\
+td,th{padding:2px 8px;text-align:left}\
+pre{margin:0}\
+.hit{background:#d4f7d4}\
+.miss{background:#f7d4d4}\
+.count{color:#777;text-align:right}";
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// the file name used for the html page of a source file
+pub fn html_page_name(path: &str) -> String {
+    format!("{}.html", path.replace(['/', '\\'], "_"))
+}
+
+/// Writes the index page of the html report
+pub fn to_html_index(report: &BTreeMap<String, FileCoverage>) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Coverage</title>\
+         <style>{}</style></head><body>\n<h1>Coverage</h1>\n<table>\n\
+         <tr><th>File</th><th>Lines</th><th>Branches</th></tr>",
+        HTML_STYLE
+    )
+    .unwrap();
+
+    for (path, file) in report {
+        writeln!(
+            output,
+            "<tr><td><a href=\"{}\">{}</a></td><td>{:.2}% ({}/{})</td><td>{:.2}% ({}/{})</td></tr>",
+            html_page_name(path),
+            escape_html(path),
+            percent(file.lines_hit(), file.lines.len()),
+            file.lines_hit(),
+            file.lines.len(),
+            percent(file.branches_hit(), file.branch_count()),
+            file.branches_hit(),
+            file.branch_count()
+        )
+        .unwrap();
+    }
+
+    output += "</table>\n</body></html>\n";
+    output
+}
+
+/// Writes the html page of a single source file, `code` is the contents of the
+/// file
+pub fn to_html_page(path: &str, file: &FileCoverage, code: &str) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title>\
+         <style>{1}</style></head><body>\n<h1>{0}</h1>\n<p><a href=\"index.html\">back</a></p>\n<table>",
+        escape_html(path),
+        HTML_STYLE
+    )
+    .unwrap();
+
+    for (index, text) in code.lines().enumerate() {
+        let line = index as u32 + 1;
+
+        let (class, count) = match file.lines.get(&line) {
+            Some(0) => ("miss", "0".to_string()),
+            Some(count) => ("hit", count.to_string()),
+            None => ("", String::new()),
+        };
+
+        writeln!(
+            output,
+            "<tr class=\"{}\"><td class=\"count\">{}</td><td class=\"count\">{}</td><td><pre>{}</pre></td></tr>",
+            class,
+            line,
+            count,
+            escape_html(text)
+        )
+        .unwrap();
+    }
+
+    output += "</table>\n</body></html>\n";
+    output
+}
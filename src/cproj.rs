@@ -2,17 +2,49 @@ mod action;
 mod actions;
 mod arg_retriever;
 mod config;
+mod config_edit;
+mod config_file;
+mod coverage;
+mod crash;
+mod diff;
+mod effective;
+mod entry_point;
+mod export;
+mod glob;
 mod graph;
+mod import;
+mod platform;
+mod profile;
+mod runner;
+mod sha256;
+mod shell_words;
+mod template;
+mod vcs;
+mod watcher;
 
-use std::env;
+use std::{env, process};
 
 use action::Action;
 use actions::*;
 
-/// cproj new --name [name] --lang [c, cpp] (default = --lang c)
-/// cproj init --name [name] --lang [c, cpp] (default = --lang c)
+/// cproj new --name [name] --lang [c, cpp] --template [name] --vcs [git, none] --format [json, toml]
+/// cproj init --name [name] --lang [c, cpp] --vcs [git, none] --format [json, toml]
 /// cproj run [--debug, --release] (default = --debug)
-/// cproj build [--debug, --release] (default = --debug)
+/// cproj build [--debug, --release] [--backend cproj, ninja] (default = --debug)
+/// cproj test [--debug, --release] [--bless] [case names] (default = --debug)
+/// cproj bench [--iterations n] [--save-baseline name] [--baseline name] [bench names]
+/// cproj coverage [--release] [--fail-under percent]
+/// cproj fuzz [--target] target [--runs n] [--max-total-time seconds]
+/// cproj check [--debug, --release] (default = --debug)
+/// cproj watch [build, run, test, check] [args] (default = build)
+/// cproj debug [--break file:line] [--debugger name] [-- args]
+/// cproj install [--prefix path] [--debug, --release] (default = --prefix /usr/local)
+/// cproj uninstall
+/// cproj package [--source]
+/// cproj export [cmake, make] [--force]
+/// cproj import [--name name] [compile_commands.json, makefile]
+/// cproj migrate
+/// cproj config [show, get, set, add, remove] [field] [values]
 /// cproj clean
 /// cproj help
 ///
@@ -26,6 +58,7 @@ fn main() {
             if let Err(err) = action.undo() {
                 eprintln!("Error: {}", err.get_msg());
             }
+            process::exit(err.get_exit_code().unwrap_or(1));
         }
     } else {
         eprintln!("Invalid Command!");
@@ -59,6 +92,20 @@ fn validate_command(command: &str, args: &[&str]) -> Option<Box<dyn Action>> {
         "init" => Some(InitProject::new(args)),
         "run" => Some(RunProject::new(args)),
         "build" => Some(BuildProject::new(args)),
+        "test" => Some(TestProject::new(args)),
+        "bench" => Some(BenchProject::new(args)),
+        "coverage" => Some(CoverageProject::new(args)),
+        "fuzz" => Some(FuzzProject::new(args)),
+        "check" => Some(CheckProject::new(args)),
+        "debug" => Some(DebugProject::new(args)),
+        "install" => Some(InstallProject::new(args)),
+        "uninstall" => Some(UninstallProject::new()),
+        "package" => Some(PackageProject::new(args)),
+        "export" => Some(ExportProject::new(args)),
+        "import" => Some(ImportProject::new(args)),
+        "migrate" => Some(MigrateProject::new()),
+        "config" => Some(ConfigProject::new(args)),
+        "watch" => Some(WatchProject::new(args)),
         "clean" => Some(CleanProject::new()),
         "help" => Some(Help::new()),
         _ => None,
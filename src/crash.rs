@@ -0,0 +1,392 @@
+use std::{
+    fs,
+    path::{self, PathBuf},
+    process,
+    time::{Duration, SystemTime},
+};
+
+#[cfg(unix)]
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+use crate::{
+    arg_retriever::{ArgRetriever, ArgRule},
+    glob,
+    profile::Profile,
+};
+
+/// the signal that terminated a process, only processes on unix are
+/// terminated by signals
+#[cfg(unix)]
+pub fn signal(status: &process::ExitStatus) -> Option<i32> {
+    status.signal()
+}
+
+#[cfg(not(unix))]
+pub fn signal(_status: &process::ExitStatus) -> Option<i32> {
+    None
+}
+
+#[cfg(unix)]
+fn core_dumped(status: &process::ExitStatus) -> bool {
+    status.core_dumped()
+}
+
+#[cfg(not(unix))]
+fn core_dumped(_status: &process::ExitStatus) -> bool {
+    false
+}
+
+/// the name of a signal along with a short description of it
+#[cfg(unix)]
+pub fn signal_name(signal: i32) -> Option<(&'static str, &'static str)> {
+    let name = match signal {
+        libc::SIGHUP => ("SIGHUP", "hangup"),
+        libc::SIGINT => ("SIGINT", "interrupt"),
+        libc::SIGQUIT => ("SIGQUIT", "quit"),
+        libc::SIGILL => ("SIGILL", "illegal instruction"),
+        libc::SIGTRAP => ("SIGTRAP", "trace trap"),
+        libc::SIGABRT => ("SIGABRT", "aborted"),
+        libc::SIGBUS => ("SIGBUS", "bus error"),
+        libc::SIGFPE => ("SIGFPE", "floating point exception"),
+        libc::SIGKILL => ("SIGKILL", "killed"),
+        libc::SIGUSR1 => ("SIGUSR1", "user defined signal 1"),
+        libc::SIGSEGV => ("SIGSEGV", "segmentation fault"),
+        libc::SIGUSR2 => ("SIGUSR2", "user defined signal 2"),
+        libc::SIGPIPE => ("SIGPIPE", "broken pipe"),
+        libc::SIGALRM => ("SIGALRM", "alarm clock"),
+        libc::SIGTERM => ("SIGTERM", "terminated"),
+        libc::SIGXCPU => ("SIGXCPU", "cpu time limit exceeded"),
+        libc::SIGXFSZ => ("SIGXFSZ", "file size limit exceeded"),
+        libc::SIGSYS => ("SIGSYS", "bad system call"),
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+#[cfg(not(unix))]
+pub fn signal_name(_signal: i32) -> Option<(&'static str, &'static str)> {
+    None
+}
+
+/// describes the signal that terminated a process, such as
+/// "SIGSEGV (segmentation fault)"
+pub fn describe_signal(signal: i32) -> String {
+    match signal_name(signal) {
+        Some((name, description)) => format!("{} ({})", name, description),
+        None => format!("signal {}", signal),
+    }
+}
+
+/// What to do when the project binary is killed by a signal, on top of saying
+/// which signal it was
+///
+#[derive(Clone, Default)]
+pub struct CrashReport {
+    backtrace: bool,
+    core_dump: bool,
+}
+
+impl CrashReport {
+    /// the argument rules used by [`CrashReport::from_args()`]
+    pub fn arg_rules() -> Vec<ArgRule> {
+        vec![
+            ArgRule::new("--backtrace", 0),
+            ArgRule::new("--core-dump", 0),
+        ]
+    }
+
+    /// core dumps of optimized binaries are of little use, so they are only
+    /// enabled for debug builds
+    pub fn from_args(arg_ret: &ArgRetriever, profile: &Profile) -> Self {
+        Self {
+            backtrace: arg_ret.has_tag("--backtrace"),
+            core_dump: arg_ret.has_tag("--core-dump") && !profile.is_release(),
+        }
+    }
+
+    /// sets up the command of the binary so that it can be reported on if it
+    /// crashes
+    pub fn prepare(&self, cmd: &mut process::Command) {
+        if self.core_dump {
+            Self::raise_core_limit(cmd);
+        }
+    }
+
+    // core dumps are often turned off with ulimit -c 0, so the limit is raised
+    // for the binary alone
+    #[cfg(unix)]
+    fn raise_core_limit(cmd: &mut process::Command) {
+        // SAFETY: setrlimit is async signal safe and only touches the child
+        unsafe {
+            cmd.pre_exec(|| {
+                let limit = libc::rlimit {
+                    rlim_cur: libc::RLIM_INFINITY,
+                    rlim_max: libc::RLIM_INFINITY,
+                };
+
+                // fall back to raising the soft limit as far as it may go
+                if libc::setrlimit(libc::RLIMIT_CORE, &limit) != 0 {
+                    let mut current = limit;
+                    if libc::getrlimit(libc::RLIMIT_CORE, &mut current) == 0 {
+                        current.rlim_cur = current.rlim_max;
+                        libc::setrlimit(libc::RLIMIT_CORE, &current);
+                    }
+                }
+
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn raise_core_limit(_cmd: &mut process::Command) {}
+
+    /// Prints what is known about a crashed binary, does nothing if the binary
+    /// wasnt killed by a signal
+    ///
+    /// `pid` and `started` are used to find its core dump, `args` and `stdin`
+    /// are used to run it again under gdb for a backtrace
+    pub fn report(
+        &self,
+        bin_path: &path::Path,
+        args: &[String],
+        stdin: Option<&path::Path>,
+        status: process::ExitStatus,
+        pid: u32,
+        started: SystemTime,
+    ) {
+        if signal(&status).is_none() {
+            return;
+        }
+
+        if self.core_dump {
+            if core_dumped(&status) {
+                // file times are coarser than the clock, so a core written
+                // right after the binary started can look older than it
+                let started = started - Duration::from_secs(1);
+
+                match Self::find_core(bin_path, pid, started) {
+                    Ok(core_path) => {
+                        println!("=> Analyzing core dump {}...", core_path.to_str().unwrap());
+
+                        let mut gdb = Self::gdb_command();
+                        gdb.args(["-ex", "bt"]).arg(bin_path).arg(&core_path);
+                        Self::print_backtrace(gdb);
+                        return;
+                    }
+                    Err(msg) => println!("    -> {}", msg),
+                }
+            } else {
+                println!("    -> no core dump was written, check ulimit -c");
+            }
+        }
+
+        if self.backtrace {
+            println!("=> Collecting Backtrace...");
+
+            let mut gdb = Self::gdb_command();
+            gdb.args(["-ex", "run", "-ex", "bt", "--args"])
+                .arg(bin_path)
+                .args(args);
+
+            match stdin.map(fs::File::open) {
+                Some(Ok(file)) => {
+                    gdb.stdin(file);
+                }
+                Some(Err(_)) => {
+                    println!("    -> failed to open {}", stdin.unwrap().to_str().unwrap());
+                    return;
+                }
+                None => {}
+            }
+
+            Self::print_backtrace(gdb);
+        }
+    }
+
+    fn gdb_command() -> process::Command {
+        let mut gdb = process::Command::new("gdb");
+        gdb.args(["-batch", "-nx", "-q"]);
+        gdb
+    }
+
+    // prints the frames of a gdb backtrace, along with the source line of every
+    // frame that is inside of src
+    fn print_backtrace(mut gdb: process::Command) {
+        gdb.stdout(process::Stdio::piped())
+            .stderr(process::Stdio::null());
+
+        let output = match gdb.output() {
+            Ok(val) => val,
+            Err(_) => {
+                println!("    -> failed to run gdb, is it installed?");
+                return;
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let frames: Vec<_> = stdout
+            .lines()
+            .filter(|line| line.starts_with('#'))
+            .collect();
+
+        if frames.is_empty() {
+            println!("    -> gdb did not produce a backtrace");
+            return;
+        }
+
+        for frame in frames {
+            println!("    -> {}", frame);
+
+            if let Some(line) = Self::source_line(frame) {
+                println!("           {}", line.trim());
+            }
+        }
+    }
+
+    // "#0  0x... in crash (p=0x0) at src/work.c:5" -> the code on line 5 of
+    // src/work.c
+    fn source_line(frame: &str) -> Option<String> {
+        let (_, location) = frame.rsplit_once(" at ")?;
+        let (file, line) = location.trim().rsplit_once(':')?;
+        let line: usize = line.parse().ok()?;
+
+        let file = path::Path::new(file);
+        let file = file
+            .strip_prefix(std::env::current_dir().ok()?)
+            .unwrap_or(file);
+
+        if !file.starts_with("src") {
+            return None;
+        }
+
+        fs::read_to_string(file)
+            .ok()?
+            .lines()
+            .nth(line.checked_sub(1)?)
+            .map(|val| val.to_string())
+    }
+
+    // follows the kernel's core_pattern to where the core dump was written
+    fn find_core(
+        bin_path: &path::Path,
+        pid: u32,
+        started: SystemTime,
+    ) -> std::result::Result<PathBuf, String> {
+        let pattern = fs::read_to_string("/proc/sys/kernel/core_pattern")
+            .map(|val| val.trim().to_string())
+            .unwrap_or_else(|_| "core".to_string());
+
+        if let Some(handler) = pattern.strip_prefix('|') {
+            let handler = handler.split_whitespace().next().unwrap_or(handler);
+            return Err(format!(
+                "core dumps are handled by {}, it can't be analyzed automatically",
+                handler
+            ));
+        }
+
+        // the kernel truncates the name of the executable to 15 characters
+        let exe_name: String = bin_path
+            .file_name()
+            .map(|val| val.to_string_lossy().chars().take(15).collect())
+            .unwrap_or_default();
+
+        let mut core_path = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                core_path.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('p') | Some('P') => core_path += &pid.to_string(),
+                Some('e') => core_path += &exe_name,
+                Some('%') => core_path.push('%'),
+                // anything else (times, hostnames, ...) can't be worked out
+                // afterwards, so look for the newest core instead
+                _ => return Self::find_newest_core(&pattern, started),
+            }
+        }
+
+        if Self::uses_pid() && !pattern.contains("%p") {
+            core_path += &format!(".{}", pid);
+        }
+
+        // an old core from an earlier crash may still be lying around
+        let core_path = PathBuf::from(core_path);
+        let is_new = fs::metadata(&core_path)
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| modified >= started);
+
+        if core_path.is_file() && is_new {
+            Ok(core_path)
+        } else {
+            Self::find_newest_core(&pattern, started)
+        }
+    }
+
+    // whether the kernel adds the pid to the names of core dumps whose pattern
+    // doesnt hold it
+    fn uses_pid() -> bool {
+        fs::read_to_string("/proc/sys/kernel/core_uses_pid").is_ok_and(|val| val.trim() == "1")
+    }
+
+    // looks for the newest file in the folder of the core pattern whose name
+    // fits the pattern, with every specifier standing for anything
+    fn find_newest_core(
+        pattern: &str,
+        started: SystemTime,
+    ) -> std::result::Result<PathBuf, String> {
+        let pattern_path = path::Path::new(pattern);
+        let dir = match pattern_path.parent() {
+            Some(val) if !val.as_os_str().is_empty() => val.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let dir_str = dir.to_str().unwrap();
+        if dir_str.contains('%') {
+            return Err(format!(
+                "the core dump folder {} depends on the crash, it can't be searched",
+                dir_str
+            ));
+        }
+
+        let file_name = pattern_path
+            .file_name()
+            .map_or("core".to_string(), |val| val.to_string_lossy().to_string());
+        let mut name_pattern = String::new();
+        let mut chars = file_name.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                name_pattern.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('%') => name_pattern.push('%'),
+                _ => name_pattern.push('*'),
+            }
+        }
+        if Self::uses_pid() && !pattern.contains("%p") {
+            name_pattern += ".*";
+        }
+
+        fs::read_dir(&dir)
+            .map_err(|_| format!("failed to read {}", dir_str))?
+            .flatten()
+            .filter(|entry| glob::matches(&name_pattern, &entry.file_name().to_string_lossy()))
+            .filter_map(|entry| {
+                let modified = entry.metadata().and_then(|meta| meta.modified()).ok()?;
+                (modified >= started).then_some((modified, entry.path()))
+            })
+            .max()
+            .map(|(_, path)| path)
+            .ok_or_else(|| {
+                format!(
+                    "failed to find a core dump matching {} in {}",
+                    name_pattern, dir_str
+                )
+            })
+    }
+}
@@ -0,0 +1,142 @@
+/// Number of unchanged lines shown around each change
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Produces a unified diff between `old` and `new`, returns an empty string if
+/// they are identical
+///
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    let old_lines: Vec<_> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<_> = new.split_inclusive('\n').collect();
+
+    // (op, line, old position before the op, new position before the op)
+    let mut script = vec![];
+    let (mut old_pos, mut new_pos) = (0, 0);
+
+    for op in edit_script(&old_lines, &new_lines) {
+        let line = match op {
+            Op::Equal | Op::Delete => old_lines[old_pos],
+            Op::Insert => new_lines[new_pos],
+        };
+
+        script.push((op, line, old_pos, new_pos));
+
+        match op {
+            Op::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            Op::Delete => old_pos += 1,
+            Op::Insert => new_pos += 1,
+        }
+    }
+
+    let mut output = format!("--- {}\n+++ {}\n", old_name, new_name);
+
+    for (start, end) in hunk_ranges(&script) {
+        let hunk = &script[start..end];
+        let old_len = hunk.iter().filter(|(op, ..)| *op != Op::Insert).count();
+        let new_len = hunk.iter().filter(|(op, ..)| *op != Op::Delete).count();
+
+        output += &format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(hunk[0].2, old_len),
+            hunk_range(hunk[0].3, new_len)
+        );
+
+        for (op, line, ..) in hunk {
+            let prefix = match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+
+            output.push(prefix);
+            output += line;
+
+            if !line.ends_with('\n') {
+                output += "\n\\ No newline at end of file\n";
+            }
+        }
+    }
+
+    output
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    // an empty range refers to the line before it
+    if len == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, len)
+    }
+}
+
+// groups the changes in the script into hunks, changes that are close enough
+// for their context to overlap share a hunk
+fn hunk_ranges(script: &[(Op, &str, usize, usize)]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+
+    for (index, _) in script
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, ..))| *op != Op::Equal)
+    {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(script.len());
+
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+}
+
+// longest common subsequence based edit script
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let (n, m) = (old.len(), new.len());
+
+    // lcs[i][j] is the length of the lcs of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut script = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            script.push(Op::Equal);
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            script.push(Op::Delete);
+            i += 1;
+        } else {
+            script.push(Op::Insert);
+            j += 1;
+        }
+    }
+
+    script
+}
@@ -0,0 +1,366 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{env, fmt, fs, path::PathBuf};
+
+use crate::{
+    action::Result,
+    config::{self, Config, Lang},
+    config_file,
+    platform::Platform,
+    shell_words,
+};
+
+/// The user's own defaults in `~/.config/cproj/config.json`, which apply to
+/// every project
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// the compiler of c projects that dont set cc
+    #[serde(default)]
+    pub cc: Option<String>,
+    /// the compiler of cpp projects that dont set cc
+    #[serde(default)]
+    pub cxx: Option<String>,
+    /// added to the flags of every profile
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// added to the link flags
+    #[serde(default)]
+    pub link_flags: Vec<String>,
+    /// the debugger of projects that dont set one
+    #[serde(default)]
+    pub debugger: Option<String>,
+}
+
+impl UserConfig {
+    pub fn path() -> Option<PathBuf> {
+        config::user_dir().map(|dir| dir.join("config.json"))
+    }
+
+    /// Loads the user config, it is not an error for there to be none
+    pub fn load() -> Result<Option<Self>> {
+        let Some(path) = Self::path().filter(|path| path.is_file()) else {
+            return Ok(None);
+        };
+        let path_str = path.to_str().unwrap();
+
+        let contents =
+            fs::read_to_string(&path).map_err(|_| format!("failed to read {}", path_str))?;
+        config_file::Format::Json
+            .deserialize(&contents)
+            .map(Some)
+            .map_err(|err| err.prepend(&format!("failed to parse {}: ", path_str)))
+    }
+}
+
+/// Where a value of the effective config came from
+#[derive(Clone)]
+pub enum Origin {
+    Default,
+    /// the project config, named by its file name
+    Project(String),
+    /// the user config, named by its path
+    User(String),
+    /// an environment variable, either one cproj reads such as CC or one the
+    /// project config refers to with ${env:VAR}
+    Env(String),
+    /// a conditional section of the project config, by its index
+    Conditional(usize),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Project(name) | Self::User(name) => write!(f, "{}", name),
+            Self::Env(var) => write!(f, "${}", var),
+            Self::Conditional(i) => write!(f, "conditional[{}]", i),
+        }
+    }
+}
+
+/// Each field of the effective config along with where its value came from,
+/// in the order of the config. Fields that several layers add to name all of
+/// them
+pub type Origins = Vec<(String, Vec<Origin>)>;
+
+/// Works out the config a project is built with, from lowest to highest
+/// priority
+///
+/// 1. the defaults of every field
+/// 2. the user config, which fills in cc and the debugger when the project
+///    doesnt set them and adds its flags to the ones of the project
+/// 3. the project config, where ${env:VAR} is replaced by the value of VAR
+/// 4. CC or CXX, which replace cc, and CFLAGS or CXXFLAGS and LDFLAGS, which
+///    are added to the flags, like they are for make. Any words after the
+///    first one of CC or CXX go before the flags
+///
+/// The conditional sections of the project config whose platform matches
+/// are added once cc is known, since the platform is read from it. They go
+/// before the flags from the environment
+///
+/// `fields` is the project config as it is written, which tells the fields
+/// it sets apart from the defaults
+///
+pub fn resolve(
+    config: Config,
+    fields: &Value,
+    project: &str,
+    user: Option<&UserConfig>,
+) -> Result<(Config, Origins)> {
+    let mut layered: Vec<(String, Origin, bool)> = vec![];
+
+    let mut value = serde_json::to_value(&config).unwrap();
+    let mut interpolated = vec![];
+    interpolate(&mut value, "", &mut interpolated)?;
+    let mut config = config_file::from_value(value)?;
+    for (field, var) in interpolated {
+        layered.push((field_name(&field).to_string(), Origin::Env(var), false));
+    }
+
+    let is_set = |field: &str| lookup(fields, field).is_some();
+    const FLAGS: [&str; 2] = ["profiles.debug.flags", "profiles.release.flags"];
+
+    if let Some(user) = user {
+        let origin = Origin::User(UserConfig::path().unwrap().to_str().unwrap().to_string());
+
+        let cc = match config.lang {
+            Lang::C => &user.cc,
+            Lang::Cpp => &user.cxx,
+        };
+        if let (Some(cc), false) = (cc, is_set("cc")) {
+            config.cc = cc.clone();
+            layered.push(("cc".to_string(), origin.clone(), true));
+        }
+
+        if let (Some(debugger), false) = (&user.debugger, is_set("debugger")) {
+            config.debugger = Some(debugger.clone());
+            layered.push(("debugger".to_string(), origin.clone(), true));
+        }
+
+        if !user.flags.is_empty() {
+            config.profiles.debug.flags.extend(user.flags.clone());
+            config.profiles.release.flags.extend(user.flags.clone());
+            layered.extend(FLAGS.map(|field| (field.to_string(), origin.clone(), false)));
+        }
+
+        if !user.link_flags.is_empty() {
+            config.link_flags.extend(user.link_flags.clone());
+            layered.push(("link_flags".to_string(), origin, false));
+        }
+    }
+
+    let (cc_var, flags_var) = match config.lang {
+        Lang::C => ("CC", "CFLAGS"),
+        Lang::Cpp => ("CXX", "CXXFLAGS"),
+    };
+
+    // CC may hold a launcher or flags as well, such as "ccache gcc", which
+    // come before the flags of the profiles
+    if let Some(cc) = env_var(cc_var) {
+        let mut words = shell_words::split(&cc)
+            .map_err(|err| err.prepend(&format!("invalid {}: ", cc_var)))?
+            .into_iter();
+        config.cc = words
+            .next()
+            .filter(|cc| !cc.is_empty())
+            .ok_or_else(|| format!("invalid {}: it doesnt name a compiler", cc_var))?;
+        layered.push(("cc".to_string(), Origin::Env(cc_var.to_string()), true));
+
+        let flags: Vec<_> = words.collect();
+        if !flags.is_empty() {
+            for profile in [&mut config.profiles.debug, &mut config.profiles.release] {
+                profile.flags.splice(0..0, flags.clone());
+            }
+            layered.extend(
+                FLAGS.map(|field| (field.to_string(), Origin::Env(cc_var.to_string()), false)),
+            );
+        }
+    }
+
+    if !config.conditional.is_empty() {
+        let platform = Platform::detect(&config.cc);
+        let sections = config.conditional.clone();
+
+        for (i, section) in sections.into_iter().enumerate() {
+            if !platform.matches(&section.when) {
+                continue;
+            }
+
+            let origin = Origin::Conditional(i);
+            let mut flags = section.flags;
+            flags.extend(section.defines.iter().map(|define| format!("-D{}", define)));
+
+            for (field, values, added) in [
+                ("include", &mut config.include, section.include),
+                ("lib", &mut config.lib, section.lib),
+                ("link_flags", &mut config.link_flags, section.link_flags),
+                ("exclude", &mut config.exclude, section.exclude),
+                (
+                    "profiles.debug.flags",
+                    &mut config.profiles.debug.flags,
+                    flags.clone(),
+                ),
+                (
+                    "profiles.release.flags",
+                    &mut config.profiles.release.flags,
+                    flags,
+                ),
+            ] {
+                if !added.is_empty() {
+                    values.extend(added);
+                    layered.push((field.to_string(), origin.clone(), false));
+                }
+            }
+
+            if !section.overrides.is_empty() {
+                config.overrides.extend(section.overrides);
+                layered.push(("overrides".to_string(), origin, false));
+            }
+        }
+    }
+
+    if let Some(flags) = env_var(flags_var) {
+        let flags = shell_words::split(&flags)
+            .map_err(|err| err.prepend(&format!("invalid {}: ", flags_var)))?;
+        config.profiles.debug.flags.extend(flags.clone());
+        config.profiles.release.flags.extend(flags);
+        layered.extend(
+            FLAGS.map(|field| (field.to_string(), Origin::Env(flags_var.to_string()), false)),
+        );
+    }
+
+    if let Some(flags) = env_var("LDFLAGS") {
+        let flags = shell_words::split(&flags).map_err(|err| err.prepend("invalid LDFLAGS: "))?;
+        config.link_flags.extend(flags);
+        layered.push((
+            "link_flags".to_string(),
+            Origin::Env("LDFLAGS".to_string()),
+            false,
+        ));
+    }
+
+    // every field starts out from the project or the default, then gets what
+    // the other layers did to it
+    let mut origins = vec![];
+    leaves(&serde_json::to_value(&config).unwrap(), "", &mut origins);
+    let mut origins: Origins = origins
+        .into_iter()
+        .map(|field| {
+            let origin = if is_set(&field) {
+                Origin::Project(project.to_string())
+            } else {
+                Origin::Default
+            };
+            (field, vec![origin])
+        })
+        .collect();
+
+    for (field, origin, replaces) in layered {
+        if let Some((_, field_origins)) = origins.iter_mut().find(|(name, _)| *name == field) {
+            if replaces {
+                field_origins.clear();
+            }
+            field_origins.push(origin);
+        }
+    }
+
+    Ok((config, origins))
+}
+
+/// the value of a field such as `profiles.debug.flags`
+pub fn lookup<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(value, |value, name| value.as_object()?.get(name))
+}
+
+// variables that are set but empty count as unset, like they do for make
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|val| !val.trim().is_empty())
+}
+
+// the fields of a config down to values that arent tables, lists count as a
+// single value
+fn leaves(value: &Value, prefix: &str, output: &mut Vec<String>) {
+    match value.as_object() {
+        Some(fields) => {
+            for (name, value) in fields {
+                leaves(value, &join(prefix, name), output);
+            }
+        }
+        None => output.push(prefix.to_string()),
+    }
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+// the field an element of a list belongs to, flags[1] is part of flags
+fn field_name(field: &str) -> &str {
+    field.split_once('[').map_or(field, |(name, _)| name)
+}
+
+// replaces ${env:VAR} in every string, collecting the fields and variables
+// that were used
+fn interpolate(value: &mut Value, field: &str, used: &mut Vec<(String, String)>) -> Result<()> {
+    match value {
+        Value::String(text) => {
+            let (filled, vars) =
+                interpolate_str(text).map_err(|err| err.prepend(&format!("{}: ", field)))?;
+            *text = filled;
+            used.extend(vars.into_iter().map(|var| (field.to_string(), var)));
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter_mut().enumerate() {
+                interpolate(value, &format!("{}[{}]", field, i), used)?;
+            }
+        }
+        Value::Object(fields) => {
+            for (name, value) in fields.iter_mut() {
+                interpolate(value, &join(field, name), used)?;
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Fills in every ${env:VAR} in a string with the value of VAR, giving the
+/// variables that were used
+/// # Error
+/// If a variable isnt set, since building with part of a flag missing would
+/// fail in ways that are much harder to track down
+///
+pub fn interpolate_str(text: &str) -> Result<(String, Vec<String>)> {
+    const START: &str = "${env:";
+
+    let mut filled = String::new();
+    let mut vars = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find(START) {
+        filled += &rest[..start];
+        rest = &rest[start + START.len()..];
+
+        let Some(end) = rest.find('}') else {
+            return Err(format!("{}{} is missing its closing }}", START, rest).into());
+        };
+        let var = &rest[..end];
+        let val =
+            env::var(var).map_err(|_| format!("the environment variable {} is not set", var))?;
+
+        filled += &val;
+        vars.push(var.to_string());
+        rest = &rest[end + 1..];
+    }
+    filled += rest;
+
+    Ok((filled, vars))
+}
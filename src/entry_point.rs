@@ -0,0 +1,128 @@
+use std::{fs, path};
+
+/// Checks if a c/c++ source file defines a main function
+///
+/// This doesnt run the preprocessor so it can be fooled by macros, but it does
+/// skip over comments, string literals and anything that isnt at file scope
+///
+pub fn defines_main(code: &str) -> bool {
+    let code = strip_comments_and_literals(code);
+    let chars: Vec<_> = code.chars().collect();
+
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '{' => depth += 1,
+            '}' => depth = usize::saturating_sub(depth, 1),
+            c if depth == 0 && is_ident_char(c) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+
+                let ident: String = chars[start..i].iter().collect();
+                if ident == "main" && is_definition(&chars[i..]) {
+                    return true;
+                }
+
+                continue;
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    false
+}
+
+/// Finds the source file that defines main
+pub fn find_main<'a, I>(paths: I) -> Option<path::PathBuf>
+where
+    I: IntoIterator<Item = &'a path::PathBuf>,
+{
+    paths
+        .into_iter()
+        .find(|path| {
+            fs::read_to_string(path)
+                .map(|code| defines_main(&code))
+                .unwrap_or(false)
+        })
+        .cloned()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// checks that what follows an identifier is a parameter list and a body,
+// as opposed to a declaration or a call
+fn is_definition(rest: &[char]) -> bool {
+    let mut rest = rest.iter().skip_while(|c| c.is_whitespace());
+
+    if rest.next() != Some(&'(') {
+        return false;
+    }
+
+    let mut depth = 1;
+    for c in rest.by_ref() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+
+        if depth == 0 {
+            break;
+        }
+    }
+
+    rest.find(|c| !c.is_whitespace()) == Some(&'{')
+}
+
+// replaces comments with a space and empties string/char literals so that
+// nothing inside of them gets mistaken for code
+fn strip_comments_and_literals(code: &str) -> String {
+    let mut output = String::with_capacity(code.len());
+    let mut chars = code.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = '\0';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+                output.push(' ');
+            }
+            '"' | '\'' => {
+                let mut escaped = false;
+                for inner in chars.by_ref() {
+                    if !escaped && inner == c {
+                        break;
+                    }
+                    escaped = !escaped && inner == '\\';
+                }
+                output.push(c);
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
@@ -0,0 +1,369 @@
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{
+    config::{Config, Kind, Lang},
+    config_file, shell_words,
+};
+
+/// A build system that a project can be exported to
+#[derive(Clone, Copy)]
+pub enum Format {
+    CMake,
+    Make,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cmake" => Some(Self::CMake),
+            "make" | "makefile" => Some(Self::Make),
+            _ => None,
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::CMake => "CMakeLists.txt",
+            Self::Make => "Makefile",
+        }
+    }
+
+    /// writes the build definition of a project, `sources` are the files that
+    /// get compiled
+    pub fn generate(&self, config: &Config, sources: &[PathBuf]) -> String {
+        match self {
+            Self::CMake => to_cmake(config, sources),
+            Self::Make => to_makefile(config, sources),
+        }
+    }
+}
+
+// cmake splits unquoted arguments on whitespace and semicolons
+fn cmake_quote(arg: &str) -> String {
+    let is_safe = |c: char| !c.is_whitespace() && !"\"\\;$#()".contains(c);
+
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        arg.to_string()
+    } else {
+        format!(
+            "\"{}\"",
+            arg.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('$', "\\$")
+        )
+    }
+}
+
+fn cmake_list(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| cmake_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// the flags of each source that overrides change, in debug and in release
+// mode, starting out from the flags of the profiles
+fn overridden_flags<'a>(
+    config: &Config,
+    sources: &'a [PathBuf],
+    debug_flags: &[String],
+    release_flags: &[String],
+) -> Vec<(&'a PathBuf, Vec<String>, Vec<String>)> {
+    sources
+        .iter()
+        .filter(|source| config.overrides.iter().any(|val| val.matches(source)))
+        .map(|source| {
+            let mut debug = debug_flags.to_vec();
+            let mut release = release_flags.to_vec();
+            for file_override in config.overrides.iter().filter(|val| val.matches(source)) {
+                file_override.apply(&mut debug);
+                file_override.apply(&mut release);
+            }
+
+            (source, debug, release)
+        })
+        .collect()
+}
+
+// one argument that holds the flags of the mode cmake builds in
+fn cmake_mode_flags(debug_flags: &[String], release_flags: &[String]) -> String {
+    format!(
+        "\"$<$<CONFIG:Debug>:{}>;$<$<CONFIG:Release>:{}>\"",
+        debug_flags.join(";"),
+        release_flags.join(";")
+    )
+}
+
+fn to_cmake(config: &Config, sources: &[PathBuf]) -> String {
+    let mut output = String::new();
+    let name = &config.name;
+
+    // cpp projects may still hold c files
+    let (languages, flag_vars) = match config.lang {
+        Lang::C => ("C", vec!["CMAKE_C_FLAGS"]),
+        Lang::Cpp => ("C CXX", vec!["CMAKE_C_FLAGS", "CMAKE_CXX_FLAGS"]),
+    };
+
+    let config_path = config_file::path();
+    let config_name = config_path.to_str().unwrap();
+
+    writeln!(
+        output,
+        "# Generated by cproj export cmake from {}",
+        config_name
+    )
+    .unwrap();
+    writeln!(output, "cmake_minimum_required(VERSION 3.13)").unwrap();
+    writeln!(output, "project({} LANGUAGES {})\n", name, languages).unwrap();
+
+    writeln!(output, "# cproj builds in debug mode by default").unwrap();
+    writeln!(output, "if(NOT CMAKE_BUILD_TYPE)").unwrap();
+    writeln!(output, "  set(CMAKE_BUILD_TYPE Debug)").unwrap();
+    writeln!(output, "endif()\n").unwrap();
+
+    writeln!(
+        output,
+        "# only the flags from {} are used for each mode",
+        config_name
+    )
+    .unwrap();
+    for var in flag_vars {
+        writeln!(output, "set({}_DEBUG \"\")", var).unwrap();
+        writeln!(output, "set({}_RELEASE \"\")", var).unwrap();
+    }
+    writeln!(output).unwrap();
+
+    writeln!(output, "set(SOURCES").unwrap();
+    for source in sources {
+        writeln!(output, "  {}", cmake_quote(source.to_str().unwrap())).unwrap();
+    }
+    writeln!(output, ")\n").unwrap();
+
+    match config.kind {
+        Kind::Bin => writeln!(output, "add_executable({} ${{SOURCES}})", name),
+        Kind::StaticLib => writeln!(output, "add_library({} STATIC ${{SOURCES}})", name),
+        Kind::SharedLib => writeln!(output, "add_library({} SHARED ${{SOURCES}})", name),
+    }
+    .unwrap();
+
+    if !config.include.is_empty() {
+        writeln!(
+            output,
+            "target_include_directories({} PRIVATE {})",
+            name,
+            cmake_list(&config.include)
+        )
+        .unwrap();
+    }
+
+    if !config.lib.is_empty() {
+        writeln!(
+            output,
+            "target_link_directories({} PRIVATE {})",
+            name,
+            cmake_list(&config.lib)
+        )
+        .unwrap();
+    }
+
+    let overridden = overridden_flags(
+        config,
+        sources,
+        &config.profiles.debug.flags,
+        &config.profiles.release.flags,
+    );
+
+    if overridden.is_empty() {
+        writeln!(output, "target_compile_options({} PRIVATE", name).unwrap();
+        writeln!(
+            output,
+            "  \"$<$<CONFIG:Debug>:{}>\"",
+            config.profiles.debug.flags.join(";")
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "  \"$<$<CONFIG:Release>:{}>\"",
+            config.profiles.release.flags.join(";")
+        )
+        .unwrap();
+        writeln!(output, ")").unwrap();
+    } else {
+        // overrides can take flags out, which the options of the target
+        // cant, so every file gets its own options instead
+        writeln!(output, "set_source_files_properties(${{SOURCES}}").unwrap();
+        writeln!(
+            output,
+            "  PROPERTIES COMPILE_OPTIONS {}",
+            cmake_mode_flags(&config.profiles.debug.flags, &config.profiles.release.flags)
+        )
+        .unwrap();
+        writeln!(output, ")\n").unwrap();
+
+        writeln!(output, "# files with overrides in {}", config_name).unwrap();
+        for (source, debug_flags, release_flags) in overridden {
+            writeln!(
+                output,
+                "set_source_files_properties({}",
+                cmake_quote(source.to_str().unwrap())
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  PROPERTIES COMPILE_OPTIONS {}",
+                cmake_mode_flags(&debug_flags, &release_flags)
+            )
+            .unwrap();
+            writeln!(output, ")").unwrap();
+        }
+    }
+
+    if !config.link_flags.is_empty() {
+        writeln!(
+            output,
+            "target_link_libraries({} PRIVATE {})",
+            name,
+            cmake_list(&config.link_flags)
+        )
+        .unwrap();
+    }
+
+    writeln!(output).unwrap();
+    match config.kind {
+        Kind::Bin => writeln!(output, "install(TARGETS {} RUNTIME DESTINATION bin)", name),
+        Kind::StaticLib | Kind::SharedLib => writeln!(
+            output,
+            "install(TARGETS {0} ARCHIVE DESTINATION lib LIBRARY DESTINATION lib)\n\
+             install(DIRECTORY include/ DESTINATION include/{0} OPTIONAL)",
+            name
+        ),
+    }
+    .unwrap();
+
+    output
+}
+
+// make expands $ before the shell ever sees the recipe
+fn make_words(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| shell_words::quote(arg).replace('$', "$$"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn to_makefile(config: &Config, sources: &[PathBuf]) -> String {
+    let mut output = String::new();
+
+    let prefixed = |flag: &str, dirs: &[String]| {
+        let words: Vec<_> = dirs.iter().map(|dir| format!("{}{}", flag, dir)).collect();
+        make_words(&words)
+    };
+    let mut debug_flags = config.profiles.debug.flags.clone();
+    let mut release_flags = config.profiles.release.flags.clone();
+    if config.kind == Kind::SharedLib {
+        debug_flags.push("-fPIC".to_string());
+        release_flags.push("-fPIC".to_string());
+    }
+
+    let target = match config.kind {
+        Kind::Bin => config.name.clone(),
+        Kind::StaticLib => format!("lib{}.a", config.name),
+        Kind::SharedLib => format!("lib{}.so", config.name),
+    };
+
+    writeln!(
+        output,
+        "# Generated by cproj export make from {}",
+        config_file::path().to_str().unwrap()
+    )
+    .unwrap();
+    writeln!(output, "# run make MODE=release to build in release mode\n").unwrap();
+    writeln!(output, "CC := {}", config.cc).unwrap();
+    writeln!(output, "MODE ?= debug\n").unwrap();
+    let source_names: Vec<_> = sources
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    writeln!(output, "SRCS := {}", make_words(&source_names)).unwrap();
+    writeln!(output, "INCLUDES := {}", prefixed("-I", &config.include)).unwrap();
+    writeln!(output, "LIBDIRS := {}", prefixed("-L", &config.lib)).unwrap();
+    writeln!(output, "LINK_FLAGS := {}", make_words(&config.link_flags)).unwrap();
+    writeln!(output, "DEBUG_FLAGS := {}", make_words(&debug_flags)).unwrap();
+    writeln!(output, "RELEASE_FLAGS := {}\n", make_words(&release_flags)).unwrap();
+
+    writeln!(output, "ifeq ($(MODE),release)").unwrap();
+    writeln!(output, "FLAGS := $(RELEASE_FLAGS)").unwrap();
+    writeln!(output, "else").unwrap();
+    writeln!(output, "FLAGS := $(DEBUG_FLAGS)").unwrap();
+    writeln!(output, "endif\n").unwrap();
+
+    writeln!(output, "BUILD_DIR := build/$(MODE)").unwrap();
+    writeln!(output, "OBJS := $(patsubst %,$(BUILD_DIR)/obj/%.o,$(SRCS))").unwrap();
+    writeln!(output, "TARGET := $(BUILD_DIR)/{}\n", target).unwrap();
+
+    let overridden = overridden_flags(config, sources, &debug_flags, &release_flags);
+    if !overridden.is_empty() {
+        writeln!(
+            output,
+            "# files with overrides in {}",
+            config_file::path().to_str().unwrap()
+        )
+        .unwrap();
+        writeln!(output, "ifeq ($(MODE),release)").unwrap();
+        for (source, _, release_flags) in &overridden {
+            writeln!(
+                output,
+                "$(BUILD_DIR)/obj/{}.o: FLAGS := {}",
+                source.to_str().unwrap(),
+                make_words(release_flags)
+            )
+            .unwrap();
+        }
+        writeln!(output, "else").unwrap();
+        for (source, debug_flags, _) in &overridden {
+            writeln!(
+                output,
+                "$(BUILD_DIR)/obj/{}.o: FLAGS := {}",
+                source.to_str().unwrap(),
+                make_words(debug_flags)
+            )
+            .unwrap();
+        }
+        writeln!(output, "endif\n").unwrap();
+    }
+
+    writeln!(output, "all: $(TARGET)\n").unwrap();
+
+    writeln!(output, "$(TARGET): $(OBJS)").unwrap();
+    writeln!(output, "\t@mkdir -p $(dir $@)").unwrap();
+    match config.kind {
+        Kind::Bin => writeln!(
+            output,
+            "\t$(CC) $(OBJS) $(FLAGS) $(LIBDIRS) $(LINK_FLAGS) -o $@"
+        ),
+        Kind::StaticLib => writeln!(output, "\trm -f $@\n\tar rcs $@ $(OBJS)"),
+        Kind::SharedLib => writeln!(
+            output,
+            "\t$(CC) -shared $(OBJS) $(FLAGS) $(LIBDIRS) $(LINK_FLAGS) -o $@"
+        ),
+    }
+    .unwrap();
+    writeln!(output).unwrap();
+
+    writeln!(output, "$(BUILD_DIR)/obj/%.o: %").unwrap();
+    writeln!(output, "\t@mkdir -p $(dir $@)").unwrap();
+    writeln!(
+        output,
+        "\t$(CC) $(FLAGS) $(INCLUDES) -MMD -MP -c $< -o $@\n"
+    )
+    .unwrap();
+
+    writeln!(output, "-include $(OBJS:.o=.d)\n").unwrap();
+
+    writeln!(output, "clean:").unwrap();
+    writeln!(output, "\trm -rf build\n").unwrap();
+
+    writeln!(output, ".PHONY: all clean").unwrap();
+
+    output
+}
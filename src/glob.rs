@@ -0,0 +1,46 @@
+/// Checks if a path matches a glob pattern, both relative to the project root
+///
+/// `*` matches anything but a `/`, `**` matches anything including `/`, so
+/// `src/third_party/**` matches every file below that folder, and `?` matches
+/// a single character other than `/`. A pattern that ends in `/` matches every
+/// file below that folder as well
+///
+pub fn matches(pattern: &str, path: &str) -> bool {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+
+    let pattern = match pattern.strip_suffix('/') {
+        Some(dir) => format!("{}/**", dir),
+        None => pattern.to_string(),
+    };
+
+    let pattern: Vec<_> = pattern.chars().collect();
+    let path: Vec<_> = path.chars().collect();
+    matches_from(&pattern, &path)
+}
+
+fn matches_from(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // a/**/b also matches a/b
+            let rest = match rest {
+                ['/', after @ ..] if matches_from(after, path) => return true,
+                rest => rest,
+            };
+            (0..=path.len()).any(|i| matches_from(rest, &path[i..]))
+        }
+        ['*', rest @ ..] => {
+            let segment_end = path.iter().position(|c| *c == '/').unwrap_or(path.len());
+            (0..=segment_end).any(|i| matches_from(rest, &path[i..]))
+        }
+        ['?', rest @ ..] => match path {
+            [c, path @ ..] if *c != '/' => matches_from(rest, path),
+            _ => false,
+        },
+        [c, rest @ ..] => match path {
+            [first, path @ ..] if first == c => matches_from(rest, path),
+            _ => false,
+        },
+    }
+}
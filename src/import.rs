@@ -0,0 +1,464 @@
+use std::{
+    env, fs,
+    path::{self, Component, PathBuf},
+    process,
+};
+
+use crate::{
+    action::Result,
+    config::{Config, Kind, Lang},
+    shell_words, CompileFiles,
+};
+
+/// Where the commands of an existing build come from
+pub enum Source {
+    /// a compilation database, as written by cmake or bear
+    CompileCommands(PathBuf),
+    /// the commands make would run, the makefile is make's default when unset
+    Make(Option<PathBuf>),
+}
+
+impl Source {
+    /// picks compile_commands.json over a Makefile when both are around
+    pub fn detect() -> Option<Self> {
+        ["compile_commands.json", "build/compile_commands.json"]
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.is_file())
+            .map(Self::CompileCommands)
+            .or_else(|| {
+                ["GNUmakefile", "makefile", "Makefile"]
+                    .iter()
+                    .any(|name| path::Path::new(name).is_file())
+                    .then_some(Self::Make(None))
+            })
+    }
+
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".json") {
+            Self::CompileCommands(PathBuf::from(path))
+        } else {
+            Self::Make(Some(PathBuf::from(path)))
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::CompileCommands(path) => path.to_str().unwrap().to_string(),
+            Self::Make(Some(path)) => format!("make -nB -f {}", path.to_str().unwrap()),
+            Self::Make(None) => "make -nB".to_string(),
+        }
+    }
+
+    /// the commands of the build, along with the directory each one runs in
+    pub fn commands(&self) -> Result<Vec<BuildCommand>> {
+        match self {
+            Self::CompileCommands(path) => read_compile_commands(path),
+            Self::Make(makefile) => read_make_commands(makefile.as_deref()),
+        }
+    }
+}
+
+pub struct BuildCommand {
+    dir: PathBuf,
+    args: Vec<String>,
+}
+
+/// A cproj config worked out from the commands of another build
+pub struct Imported {
+    pub config: Config,
+    pub sources: Vec<PathBuf>,
+    /// the flags that have no place in a cproj config, with the reason why
+    pub unmapped: Vec<String>,
+}
+
+fn read_compile_commands(path: &path::Path) -> Result<Vec<BuildCommand>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|_| format!("failed to read {}", path.to_str().unwrap()))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)
+        .map_err(|_| format!("failed to parse {}", path.to_str().unwrap()))?;
+
+    let mut commands = vec![];
+    for entry in entries {
+        let dir = entry["directory"].as_str().unwrap_or(".");
+
+        // entries hold either an argument list or a single command line
+        let args = match (entry["arguments"].as_array(), entry["command"].as_str()) {
+            (Some(args), _) => args
+                .iter()
+                .filter_map(|arg| arg.as_str().map(|val| val.to_string()))
+                .collect(),
+            (None, Some(command)) => shell_words::split(command)
+                .map_err(|err| err.prepend("Invalid command in compilation database: "))?,
+            (None, None) => continue,
+        };
+
+        commands.push(BuildCommand {
+            dir: PathBuf::from(dir),
+            args,
+        });
+    }
+
+    Ok(commands)
+}
+
+fn read_make_commands(makefile: Option<&path::Path>) -> Result<Vec<BuildCommand>> {
+    // -B prints every command instead of only those of stale targets, -w
+    // reports which directory recursive makes run in
+    let mut make = process::Command::new("make");
+    make.args(["-nBw"]);
+    if let Some(makefile) = makefile {
+        make.arg("-f").arg(makefile);
+    }
+
+    let output = make
+        .stderr(process::Stdio::inherit())
+        .output()
+        .map_err(|_| "failed to run make, is it installed?")?;
+    if !output.status.success() {
+        return Err("make failed to list the commands of the build".into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut dirs = vec![env::current_dir().unwrap()];
+    let mut commands = vec![];
+    let mut line = String::new();
+
+    for part in stdout.lines() {
+        // commands may be continued over several lines
+        if let Some(part) = part.strip_suffix('\\') {
+            line += part;
+            continue;
+        }
+        line += part;
+        let current = std::mem::take(&mut line);
+
+        if let Some((_, dir)) = current.split_once(": Entering directory ") {
+            dirs.push(PathBuf::from(dir.trim_matches(|c| "'`\"".contains(c))));
+            continue;
+        }
+        if current.contains(": Leaving directory ") {
+            if dirs.len() > 1 {
+                dirs.pop();
+            }
+            continue;
+        }
+
+        // a bad line is just not a command that can be imported
+        let Ok(words) = shell_words::split(&current) else {
+            continue;
+        };
+
+        for args in words.split(|word| word == "&&" || word == ";" || word == "||") {
+            if !args.is_empty() {
+                commands.push(BuildCommand {
+                    dir: dirs.last().unwrap().clone(),
+                    args: args.to_vec(),
+                });
+            }
+        }
+    }
+
+    Ok(commands)
+}
+
+fn program_name(command: &BuildCommand) -> String {
+    path::Path::new(&command.args[0])
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// cross compilers and versioned compilers keep their usual names somewhere,
+// such as aarch64-linux-gnu-gcc or g++-12
+fn is_cpp_compiler(name: &str) -> bool {
+    name.contains("g++") || name.contains("clang++") || name.contains("c++")
+}
+
+fn is_compiler(name: &str) -> bool {
+    is_cpp_compiler(name) || name.contains("gcc") || name.contains("clang") || name == "cc"
+}
+
+fn is_archiver(name: &str) -> bool {
+    name == "ar" || name.ends_with("-ar") || name.ends_with("gcc-ar")
+}
+
+// joins `path` onto `dir` and makes it relative to the current directory if
+// it is inside of it, without touching the filesystem
+fn normalize(dir: &path::Path, path: &str) -> String {
+    let cwd = env::current_dir().unwrap();
+
+    let mut normalized = PathBuf::new();
+    for component in cwd.join(dir).join(path).components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+
+    match normalized.strip_prefix(&cwd) {
+        Ok(val) if val.as_os_str().is_empty() => ".".to_string(),
+        Ok(val) => val.to_str().unwrap().to_string(),
+        Err(_) => normalized.to_str().unwrap().to_string(),
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, val: String) {
+    if !list.contains(&val) {
+        list.push(val);
+    }
+}
+
+/// the flags of one compile command that cproj can carry over, along with the
+/// ones it cant
+#[derive(Default)]
+struct CompileFlags {
+    source: Option<String>,
+    include: Vec<String>,
+    flags: Vec<String>,
+    optimization: Option<String>,
+    unmapped: Vec<String>,
+}
+
+fn parse_compile(command: &BuildCommand) -> CompileFlags {
+    let mut parsed = CompileFlags::default();
+    let mut args = command.args[1..].iter();
+
+    while let Some(arg) = args.next() {
+        let arg = arg.as_str();
+
+        match arg {
+            "-c" => {}
+            // cproj picks where objects go and tracks headers by itself
+            "-o" | "-MF" | "-MT" | "-MQ" => {
+                args.next();
+            }
+            "-MD" | "-MMD" | "-MP" => {}
+            "-I" | "-isystem" | "-iquote" | "-idirafter" => {
+                if let Some(dir) = args.next() {
+                    push_unique(&mut parsed.include, normalize(&command.dir, dir));
+                }
+            }
+            "-D" | "-U" => {
+                if let Some(macro_def) = args.next() {
+                    parsed.flags.push(format!("{}{}", arg, macro_def));
+                }
+            }
+            _ if arg.starts_with("-I") => {
+                push_unique(&mut parsed.include, normalize(&command.dir, &arg[2..]));
+            }
+            _ if arg.starts_with("-O") => parsed.optimization = Some(arg.to_string()),
+            // debug info is turned on for debug builds by cproj
+            _ if arg.starts_with("-g") => {}
+            _ if arg.starts_with("-D")
+                || arg.starts_with("-U")
+                || arg.starts_with("-std=")
+                || arg.starts_with("-pedantic")
+                || arg.starts_with("-f")
+                || arg.starts_with("-m")
+                || (arg.starts_with("-W") && !arg.starts_with("-Wl,"))
+                || arg == "-pthread"
+                || arg == "-ansi" =>
+            {
+                parsed.flags.push(arg.to_string())
+            }
+            _ if !arg.starts_with('-') && CompileFiles::is_code_file(path::Path::new(arg)) => {
+                parsed.source = Some(normalize(&command.dir, arg));
+            }
+            // keep options together with their argument in the report
+            "-include" | "-imacros" | "-x" | "-Xpreprocessor" | "-Xassembler" | "-Xlinker" => {
+                match args.next() {
+                    Some(val) => parsed.unmapped.push(format!("{} {}", arg, val)),
+                    None => parsed.unmapped.push(arg.to_string()),
+                }
+            }
+            _ => parsed.unmapped.push(arg.to_string()),
+        }
+    }
+
+    parsed
+}
+
+/// Works out a cproj config from the commands of another build
+///
+/// Flags are only carried over if every file is compiled with them, since
+/// cproj compiles every file the same way. Everything else ends up in
+/// [`Imported::unmapped`]
+///
+pub fn import(name: &str, commands: &[BuildCommand]) -> Result<Imported> {
+    let mut unmapped = vec![];
+    let mut sources: Vec<PathBuf> = vec![];
+    let mut include = vec![];
+    let mut lib = vec![];
+    let mut link_flags = vec![];
+    let mut kind = Kind::Bin;
+    // how many files each compiler built, in the order they were first seen
+    let mut compilers: Vec<(String, usize)> = vec![];
+    let mut optimization = None;
+
+    // how many files each flag was used to compile, in the order they were
+    // first seen
+    let mut flag_counts: Vec<(String, usize)> = vec![];
+    let mut compile_count = 0;
+
+    for command in commands.iter().filter(|command| !command.args.is_empty()) {
+        let program = program_name(command);
+
+        if is_archiver(&program) {
+            kind = Kind::StaticLib;
+            continue;
+        }
+        if !is_compiler(&program) {
+            continue;
+        }
+
+        if command.args.iter().any(|arg| arg == "-c") {
+            let parsed = parse_compile(command);
+            let Some(source) = parsed.source else {
+                continue;
+            };
+
+            compile_count += 1;
+            match compilers
+                .iter_mut()
+                .find(|(val, _)| *val == command.args[0])
+            {
+                Some((_, count)) => *count += 1,
+                None => compilers.push((command.args[0].clone(), 1)),
+            }
+            sources.push(PathBuf::from(source));
+            optimization = parsed.optimization.or(optimization);
+
+            for dir in parsed.include {
+                push_unique(&mut include, dir);
+            }
+            for arg in parsed.unmapped {
+                push_unique(&mut unmapped, format!("{} (no cproj equivalent)", arg));
+            }
+
+            let mut seen = vec![];
+            for flag in parsed.flags {
+                if seen.contains(&flag) {
+                    continue;
+                }
+                match flag_counts.iter_mut().find(|(val, _)| *val == flag) {
+                    Some((_, count)) => *count += 1,
+                    None => flag_counts.push((flag.clone(), 1)),
+                }
+                seen.push(flag);
+            }
+        } else if command.args.iter().any(|arg| arg == "-o") {
+            // a command that compiles and links in one go is still a link
+            // command to cproj, its sources are found in src anyway
+            let mut args = command.args[1..].iter();
+            while let Some(arg) = args.next() {
+                let arg = arg.as_str();
+
+                match arg {
+                    "-o" => {
+                        args.next();
+                    }
+                    "-shared" => kind = Kind::SharedLib,
+                    "-L" => {
+                        if let Some(dir) = args.next() {
+                            push_unique(&mut lib, normalize(&command.dir, dir));
+                        }
+                    }
+                    _ if arg.starts_with("-L") => {
+                        push_unique(&mut lib, normalize(&command.dir, &arg[2..]))
+                    }
+                    _ if arg.starts_with("-l")
+                        || arg.starts_with("-Wl,")
+                        || arg == "-pthread"
+                        || arg == "-static"
+                        || arg == "-rdynamic" =>
+                    {
+                        push_unique(&mut link_flags, arg.to_string())
+                    }
+                    // compile flags are passed to the linker by cproj as well
+                    _ if arg.starts_with("-O")
+                        || arg.starts_with("-g")
+                        || arg.starts_with("-f")
+                        || arg.starts_with("-m")
+                        || arg.starts_with("-W")
+                        || arg.starts_with("-std=") => {}
+                    _ if !arg.starts_with('-')
+                        && (arg.ends_with(".o")
+                            || CompileFiles::is_code_file(path::Path::new(arg))) => {}
+                    _ => push_unique(&mut unmapped, format!("{} (no cproj equivalent)", arg)),
+                }
+            }
+        }
+    }
+
+    if compile_count == 0 {
+        return Err("no compile commands were found".into());
+    }
+
+    let mut flags = vec![];
+    for (flag, count) in flag_counts {
+        if count == compile_count {
+            flags.push(flag);
+        } else {
+            unmapped.push(format!(
+                "{} (only used for {} of {} files)",
+                flag, count, compile_count
+            ));
+        }
+    }
+
+    sources.sort();
+    sources.dedup();
+    for source in sources.iter().filter(|path| !path.starts_with("src")) {
+        unmapped.push(format!(
+            "{} (outside of src, cproj only builds files in src)",
+            source.to_str().unwrap()
+        ));
+    }
+
+    // max_by_key keeps the last of equal counts, so ties go to the compiler
+    // that was seen first
+    let compiler = compilers
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(compiler, _)| compiler)
+        .unwrap();
+    let compiler_name = path::Path::new(&compiler)
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+
+    let is_cpp = is_cpp_compiler(&compiler_name)
+        || sources.iter().any(|path| {
+            path.extension()
+                .is_some_and(|ext| !matches!(ext.to_str(), Some("c" | "i")))
+        });
+    let lang = if is_cpp { Lang::Cpp } else { Lang::C };
+
+    let mut config = Config::new(name, lang);
+    config.cc = compiler;
+    config.kind = kind;
+    config.include = include;
+    config.lib = lib;
+    config.link_flags = link_flags;
+
+    config.profiles.debug.flags = flags.clone();
+    config.profiles.debug.flags.push("-g".to_string());
+
+    config.profiles.release.flags = flags;
+    config.profiles.release.flags.push(match optimization {
+        Some(level) if level != "-O0" => level,
+        _ => "-O2".to_string(),
+    });
+
+    Ok(Imported {
+        config,
+        sources,
+        unmapped,
+    })
+}
@@ -0,0 +1,97 @@
+use std::{env, process};
+
+use crate::{config::When, glob};
+
+/// The platform a project is built for
+///
+/// It is read from the target triple of the compiler, so that cross
+/// compilers pick the sections of the platform they build for. Without a
+/// triple, the platform cproj runs on is used
+///
+pub struct Platform {
+    pub os: String,
+    pub arch: String,
+    /// such as x86_64-linux-gnu, as printed by `cc -dumpmachine`
+    pub target: Option<String>,
+}
+
+impl Platform {
+    pub fn detect(cc: &str) -> Self {
+        let target = process::Command::new(cc)
+            .arg("-dumpmachine")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|target| !target.is_empty());
+
+        match &target {
+            Some(triple) => Self {
+                os: Self::os_of(triple).unwrap_or(env::consts::OS).to_string(),
+                arch: Self::arch_of(triple),
+                target,
+            },
+            None => Self {
+                os: env::consts::OS.to_string(),
+                arch: env::consts::ARCH.to_string(),
+                target,
+            },
+        }
+    }
+
+    // uses the names rust uses for env::consts::OS
+    fn os_of(triple: &str) -> Option<&'static str> {
+        let names = [
+            ("linux", "linux"),
+            ("darwin", "macos"),
+            ("mingw", "windows"),
+            ("windows", "windows"),
+            ("freebsd", "freebsd"),
+            ("netbsd", "netbsd"),
+            ("openbsd", "openbsd"),
+            ("android", "android"),
+        ];
+
+        // android triples also say linux
+        names
+            .iter()
+            .rev()
+            .find(|(part, _)| triple.contains(part))
+            .map(|(_, name)| *name)
+    }
+
+    // uses the names rust uses for env::consts::ARCH
+    fn arch_of(triple: &str) -> String {
+        let arch = triple.split('-').next().unwrap_or(triple);
+
+        match arch {
+            "arm64" => "aarch64",
+            "i386" | "i486" | "i586" | "i686" => "x86",
+            "amd64" => "x86_64",
+            arch if arch.starts_with("arm") => "arm",
+            arch => arch,
+        }
+        .to_string()
+    }
+
+    /// checks if every condition of a section holds for this platform
+    pub fn matches(&self, when: &When) -> bool {
+        let holds = |condition: &Option<String>, value: Option<&str>| {
+            let Some(condition) = condition else {
+                return true;
+            };
+
+            let (expected, negated) = match condition.strip_prefix('!') {
+                Some(condition) => (condition, true),
+                None => (condition.as_str(), false),
+            };
+            let is_match = value.is_some_and(|value| glob::matches(expected, value));
+
+            is_match != negated
+        };
+
+        holds(&when.os, Some(&self.os))
+            && holds(&when.arch, Some(&self.arch))
+            && holds(&when.target, self.target.as_deref())
+    }
+}
@@ -0,0 +1,328 @@
+use std::path;
+
+use crate::{
+    action::Result,
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::{Config, Kind, Lang, ProfileSettings},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Sanitizer {
+    Address,
+    Undefined,
+    Thread,
+    Leak,
+}
+
+impl Sanitizer {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "address" | "asan" => Some(Self::Address),
+            "undefined" | "ubsan" => Some(Self::Undefined),
+            "thread" | "tsan" => Some(Self::Thread),
+            "leak" | "lsan" => Some(Self::Leak),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Address => "address",
+            Self::Undefined => "undefined",
+            Self::Thread => "thread",
+            Self::Leak => "leak",
+        }
+    }
+
+    /// parses a comma separated list of sanitizers, rejecting sanitizers that
+    /// cant be used together
+    pub fn parse_list(list: &str) -> Result<Vec<Self>> {
+        let mut sanitizers = vec![];
+
+        for name in list.split(',').filter(|name| !name.trim().is_empty()) {
+            match Self::from_name(name) {
+                Some(sanitizer) => sanitizers.push(sanitizer),
+                None => return Err(format!("unknown sanitizer {}", name.trim()).into()),
+            }
+        }
+
+        if sanitizers.is_empty() {
+            return Err("no sanitizers given".into());
+        }
+
+        sanitizers.sort();
+        sanitizers.dedup();
+
+        // tsan keeps its own shadow memory which asan and lsan cant share
+        if sanitizers.contains(&Self::Thread) {
+            if let Some(other) = sanitizers
+                .iter()
+                .find(|val| **val == Self::Address || **val == Self::Leak)
+            {
+                return Err(format!(
+                    "the thread sanitizer cant be used with the {} sanitizer",
+                    other.name()
+                )
+                .into());
+            }
+        }
+
+        Ok(sanitizers)
+    }
+}
+
+/// Describes which build of the project is being worked with and where its
+/// outputs live inside of the bin directory
+///
+#[derive(Clone)]
+pub struct Profile {
+    release_mode: bool,
+    sanitizers: Vec<Sanitizer>,
+    coverage: bool,
+    fuzz: bool,
+    check: bool,
+}
+
+impl Profile {
+    pub fn new(release_mode: bool) -> Self {
+        Self {
+            release_mode,
+            sanitizers: vec![],
+            coverage: false,
+            fuzz: false,
+            check: false,
+        }
+    }
+
+    /// only checks the project for errors, nothing gets written to obj and
+    /// nothing gets linked
+    pub fn with_check(mut self) -> Self {
+        self.check = true;
+        self
+    }
+
+    pub fn is_release(&self) -> bool {
+        self.release_mode
+    }
+
+    pub fn is_check(&self) -> bool {
+        self.check
+    }
+
+    /// instruments the build so that running it records coverage data
+    pub fn with_coverage(mut self) -> Self {
+        self.coverage = true;
+        self
+    }
+
+    /// instruments the build for libFuzzer, which needs clang and the address
+    /// sanitizer
+    pub fn with_fuzzing(mut self) -> Self {
+        self.fuzz = true;
+
+        if !self.sanitizers.contains(&Sanitizer::Address) {
+            self.sanitizers.push(Sanitizer::Address);
+            self.sanitizers.sort();
+        }
+
+        self
+    }
+
+    /// the argument rules used by [`Profile::from_args()`]
+    pub fn arg_rules() -> Vec<ArgRule> {
+        vec![ArgRule::new("--release", 0), ArgRule::new("--sanitize", 1)]
+    }
+
+    pub fn from_args(arg_ret: &ArgRetriever) -> Result<Self> {
+        let mut profile = Self::new(arg_ret.has_tag("--release"));
+
+        if let Some(args) = arg_ret.get_tag_args("--sanitize") {
+            profile.sanitizers = Sanitizer::parse_list(&args[0])?;
+        }
+
+        Ok(profile)
+    }
+
+    /// sanitized and instrumented builds get their own directory so that their
+    /// objects never mix with the objects of a plain build
+    pub fn name(&self) -> String {
+        let mut name = if self.release_mode {
+            "release".to_string()
+        } else {
+            "debug".to_string()
+        };
+
+        for sanitizer in &self.sanitizers {
+            name += "-";
+            name += sanitizer.name();
+        }
+
+        if self.coverage {
+            name += "-coverage";
+        }
+
+        if self.fuzz {
+            name += "-fuzz";
+        }
+
+        if self.check {
+            name += "-check";
+        }
+
+        name
+    }
+
+    /// bin/[profile name]
+    pub fn dir(&self) -> path::PathBuf {
+        path::PathBuf::from("bin").join(self.name())
+    }
+
+    /// the compiler used for this profile, fuzzing builds swap gcc/g++ for clang
+    pub fn cc(&self, config: &Config) -> String {
+        if !self.fuzz || config.cc.contains("clang") {
+            return config.cc.clone();
+        }
+
+        match config.lang {
+            Lang::C => "clang".to_string(),
+            Lang::Cpp => "clang++".to_string(),
+        }
+    }
+
+    pub fn bin_path(&self, config: &Config) -> path::PathBuf {
+        self.dir().join(&config.name)
+    }
+
+    /// what linking the project produces, the binary or lib[name].a/.so for
+    /// libraries
+    pub fn output_path(&self, config: &Config) -> path::PathBuf {
+        match config.kind {
+            Kind::Bin => self.bin_path(config),
+            Kind::StaticLib => self.dir().join(format!("lib{}.a", config.name)),
+            Kind::SharedLib => self.dir().join(format!("lib{}.so", config.name)),
+        }
+    }
+
+    /// where binaries built from the files in a directory other than src live,
+    /// such as benches
+    pub fn harness_dir(&self, kind: &str) -> path::PathBuf {
+        self.dir().join(kind)
+    }
+
+    pub fn timestamp_path(&self) -> path::PathBuf {
+        self.dir().join("timestamp")
+    }
+
+    pub fn linker_log_path(&self) -> path::PathBuf {
+        self.dir().join("log").join("linker.log")
+    }
+
+    pub fn obj_path(&self, src_path: &path::Path) -> path::PathBuf {
+        let mut obj_path = self.dir().join("obj").join(Self::flatten(src_path));
+        obj_path.set_extension("o");
+        obj_path
+    }
+
+    /// where the coverage data of an object file gets written when the binary
+    /// runs
+    pub fn gcda_path(&self, src_path: &path::Path) -> path::PathBuf {
+        self.obj_path(src_path).with_extension("gcda")
+    }
+
+    /// where the fingerprint of the command that compiled an object file is
+    /// kept, so that changing the flags of a file recompiles it
+    pub fn fingerprint_path(&self, src_path: &path::Path) -> path::PathBuf {
+        self.obj_path(src_path).with_extension("fingerprint")
+    }
+
+    pub fn log_path(&self, src_path: &path::Path) -> path::PathBuf {
+        let mut log_path = self.dir().join("log").join(Self::flatten(src_path));
+        log_path.set_extension("log");
+        log_path
+    }
+
+    pub fn compile_flags(&self, config: &Config) -> Vec<String> {
+        let mut flags = self.settings(config).flags.clone();
+
+        if !self.sanitizers.is_empty() {
+            flags.push(self.sanitize_flag());
+            // keeps the stack traces printed by the sanitizers readable
+            flags.push("-fno-omit-frame-pointer".to_string());
+        }
+
+        if self.coverage {
+            flags.push("--coverage".to_string());
+        }
+
+        if config.kind == Kind::SharedLib {
+            flags.push("-fPIC".to_string());
+        }
+
+        if self.check {
+            flags.push("-fsyntax-only".to_string());
+        }
+
+        flags
+    }
+
+    /// the flags a single file is compiled with, which are the flags of the
+    /// profile changed by every override that matches the file
+    pub fn file_compile_flags(&self, config: &Config, path: &path::Path) -> Vec<String> {
+        let mut flags = self.compile_flags(config);
+
+        for file_override in config.overrides.iter().filter(|val| val.matches(path)) {
+            file_override.apply(&mut flags);
+        }
+
+        flags
+    }
+
+    /// the command from the config that the binary is run through
+    pub fn runner(&self, config: &Config) -> Option<String> {
+        self.settings(config).runner.clone()
+    }
+
+    /// the settings of the mode from the config
+    pub fn settings<'a>(&self, config: &'a Config) -> &'a ProfileSettings {
+        if self.release_mode {
+            &config.profiles.release
+        } else {
+            &config.profiles.debug
+        }
+    }
+
+    pub fn link_flags(&self, config: &Config) -> Vec<String> {
+        let mut flags = config.link_flags.clone();
+
+        if !self.sanitizers.is_empty() {
+            flags.push(self.sanitize_flag());
+        }
+
+        if self.coverage {
+            flags.push("--coverage".to_string());
+        }
+
+        flags
+    }
+
+    fn sanitize_flag(&self) -> String {
+        let mut names: Vec<_> = self.sanitizers.iter().map(|val| val.name()).collect();
+
+        // libFuzzer itself only gets linked into the fuzz targets, everything
+        // else just gets instrumented for it
+        if self.fuzz {
+            names.push("fuzzer-no-link");
+        }
+
+        format!("-fsanitize={}", names.join(","))
+    }
+
+    // turns src/a/b.c into a_b.c so that every output can live in a single
+    // directory
+    fn flatten(src_path: &path::Path) -> String {
+        let output_path = src_path
+            .strip_prefix(path::PathBuf::from("src"))
+            .unwrap_or(src_path);
+        output_path.to_str().unwrap().replace('/', "_")
+    }
+}
@@ -0,0 +1,174 @@
+use std::{env, fs, io::Read, path, process};
+
+use crate::{
+    action::Result,
+    arg_retriever::{ArgRetriever, ArgRule},
+    config::Config,
+    profile::Profile,
+    shell_words,
+};
+
+/// A command that the project binary is run through, such as valgrind or qemu
+pub struct Runner {
+    words: Vec<String>,
+}
+
+impl Runner {
+    /// the argument rules used by [`Runner::from_args()`]
+    pub fn arg_rules() -> Vec<ArgRule> {
+        vec![ArgRule::new("--runner", 1)]
+    }
+
+    /// the runner given on the command line, if any
+    pub fn from_args(arg_ret: &ArgRetriever) -> Option<String> {
+        arg_ret.get_tag_args("--runner").map(|args| args[0].clone())
+    }
+
+    /// Works out what the binary of a profile should be run through
+    ///
+    /// A runner given on the command line wins over the runner of the profile
+    /// in the config. Without either, binaries built for another architecture
+    /// are run through qemu. An empty runner runs the binary directly
+    ///
+    pub fn resolve(
+        runner: Option<&str>,
+        config: &Config,
+        profile: &Profile,
+    ) -> Result<Option<Self>> {
+        let runner = match runner.map(|val| val.to_string()) {
+            Some(val) => Some(val),
+            None => profile.runner(config),
+        };
+
+        let mut words = match runner {
+            Some(runner) => {
+                shell_words::split(&runner).map_err(|err| err.prepend("Invalid runner: "))?
+            }
+            None => match Self::qemu_for(&profile.bin_path(config)) {
+                Some(qemu) => vec![qemu],
+                None => vec![],
+            },
+        };
+
+        if words.is_empty() {
+            return Ok(None);
+        }
+
+        if Self::is_memcheck(&words) {
+            Self::add_leak_checks(&mut words);
+        }
+
+        Ok(Some(Self { words }))
+    }
+
+    /// the command that runs `bin_path` through the runner, or the binary by
+    /// itself when there is no runner
+    pub fn command(runner: Option<&Self>, bin_path: &path::Path) -> process::Command {
+        match runner {
+            Some(runner) => {
+                let mut cmd = process::Command::new(&runner.words[0]);
+                cmd.args(&runner.words[1..]).arg(bin_path);
+                cmd
+            }
+            None => process::Command::new(bin_path),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        self.words
+            .iter()
+            .map(|word| shell_words::quote(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn is_memcheck(words: &[String]) -> bool {
+        let is_valgrind = path::Path::new(&words[0])
+            .file_name()
+            .is_some_and(|name| name == "valgrind");
+
+        is_valgrind
+            && words
+                .iter()
+                .filter_map(|word| word.strip_prefix("--tool="))
+                .all(|tool| tool == "memcheck")
+    }
+
+    // valgrind exits with the status of the program no matter what it finds,
+    // so leaks only fail the run if they count as errors with an exit code
+    fn add_leak_checks(words: &mut Vec<String>) {
+        let has_option = |words: &[String], option: &str| {
+            words
+                .iter()
+                .any(|word| word.starts_with(&format!("{}=", option)))
+        };
+
+        let missing: Vec<_> = [
+            ("--leak-check", "full"),
+            ("--errors-for-leak-kinds", "definite,indirect"),
+            ("--error-exitcode", "1"),
+        ]
+        .into_iter()
+        .filter(|(option, _)| !has_option(words, option))
+        .map(|(option, value)| format!("{}={}", option, value))
+        .collect();
+
+        words.splice(1..1, missing);
+    }
+
+    // the qemu user mode emulator for a binary, if it is built for an
+    // architecture the host cant run
+    fn qemu_for(bin_path: &path::Path) -> Option<String> {
+        let target = Self::elf_arch(bin_path)?;
+        let host = env::current_exe()
+            .ok()
+            .and_then(|path| Self::elf_arch(&path))?;
+
+        // 64 bit x86 hosts run 32 bit x86 binaries natively
+        if target == host || (target == "i386" && host == "x86_64") {
+            None
+        } else {
+            Some(format!("qemu-{}", target))
+        }
+    }
+
+    // the qemu name of the architecture of an elf file, taken from its header
+    fn elf_arch(path: &path::Path) -> Option<&'static str> {
+        let mut header = [0u8; 20];
+        fs::File::open(path).ok()?.read_exact(&mut header).ok()?;
+
+        if header[..4] != *b"\x7fELF" {
+            return None;
+        }
+
+        let is_64_bit = header[4] == 2;
+        let is_little_endian = header[5] == 1;
+        let machine = if is_little_endian {
+            u16::from_le_bytes([header[18], header[19]])
+        } else {
+            u16::from_be_bytes([header[18], header[19]])
+        };
+
+        let arch = match (machine, is_64_bit, is_little_endian) {
+            (3, _, _) => "i386",
+            (62, _, _) => "x86_64",
+            (40, _, true) => "arm",
+            (40, _, false) => "armeb",
+            (183, _, _) => "aarch64",
+            (8, false, true) => "mipsel",
+            (8, false, false) => "mips",
+            (8, true, true) => "mips64el",
+            (8, true, false) => "mips64",
+            (20, _, _) => "ppc",
+            (21, _, true) => "ppc64le",
+            (21, _, false) => "ppc64",
+            (22, _, _) => "s390x",
+            (243, true, _) => "riscv64",
+            (243, false, _) => "riscv32",
+            (258, _, _) => "loongarch64",
+            _ => return None,
+        };
+
+        Some(arch)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::action::Result;
+
+/// Splits a command line into words the way a POSIX shell would, handling
+/// single quotes, double quotes and backslash escapes but nothing else
+pub fn split(line: &str) -> Result<Vec<String>> {
+    let mut words = vec![];
+    let mut word = String::new();
+    // whether or not a word has been started, so that "" still makes a word
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated single quote".into()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        // only these keep their special meaning inside of
+                        // double quotes
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("unterminated double quote".into()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated double quote".into()),
+                    }
+                }
+            }
+            // a backslash before a newline continues the line without
+            // starting a word
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+                None => {
+                    in_word = true;
+                    word.push('\\');
+                }
+            },
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+/// Quotes a word so that [`split()`] (or a shell) turns it back into the same
+/// word
+pub fn quote(word: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+
+    if !word.is_empty() && word.chars().all(is_safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        split(line).unwrap_or_else(|err| panic!("{}", err.get_msg()))
+    }
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(words("  -g   -Wall\t-O2\n"), ["-g", "-Wall", "-O2"]);
+        assert!(words("   ").is_empty());
+    }
+
+    #[test]
+    fn keeps_quoted_words_together() {
+        assert_eq!(words("'hello world' x"), ["hello world", "x"]);
+        assert_eq!(words("\"a 'b' c\""), ["a 'b' c"]);
+        assert_eq!(words("-D'NAME=\"x y\"'"), ["-DNAME=\"x y\""]);
+        assert!(split("'open").is_err());
+        assert!(split("\"open").is_err());
+    }
+
+    #[test]
+    fn handles_escapes() {
+        assert_eq!(words("a\\ b c"), ["a b", "c"]);
+        assert_eq!(words("\"\\$HOME \\n\""), ["$HOME \\n"]);
+        assert_eq!(words("end\\"), ["end\\"]);
+    }
+
+    #[test]
+    fn joins_continuation_lines() {
+        assert_eq!(words("-DA \\\n    -DB"), ["-DA", "-DB"]);
+        assert_eq!(words("-DA\\\nB"), ["-DAB"]);
+        assert_eq!(words("\\\n"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn keeps_empty_quoted_words() {
+        assert_eq!(words("a \"\" ''"), ["a", "", ""]);
+    }
+
+    #[test]
+    fn quote_round_trips() {
+        for word in ["plain", "with space", "it's", "", "$VAR"] {
+            assert_eq!(words(&quote(word)), [word]);
+        }
+    }
+}
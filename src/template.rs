@@ -0,0 +1,226 @@
+use std::{
+    fs,
+    path::{self, PathBuf},
+};
+
+use crate::{
+    action::Result,
+    config::{self, Config, Kind, Lang},
+    config_file, effective,
+};
+
+/// The names of the templates that come with cproj
+pub const BUILTIN_NAMES: [&str; 4] = ["bin", "lib", "lib+tests", "cli-with-args"];
+
+/// The files a new project starts out with
+pub struct Template {
+    /// the files to create, relative to the project root
+    pub files: Vec<(PathBuf, Vec<u8>)>,
+    /// the config of the project, made from the template when it has no
+    /// cproj.json or cproj.toml of its own
+    pub config: Config,
+}
+
+impl Template {
+    /// Loads a template, user templates in `~/.config/cproj/templates/<name>`
+    /// win over the built in ones so that they can be replaced
+    pub fn load(name: &str, project_name: &str, lang: Lang) -> Result<Self> {
+        if let Some(dir) = Self::user_dir(name) {
+            return Self::from_dir(&dir, project_name, lang)
+                .map_err(|err| err.prepend(&format!("Invalid template {}: ", name)));
+        }
+
+        Self::builtin(name, project_name, lang).ok_or_else(|| {
+            format!(
+                "Unknown template {}, expected one of {} or a folder in {}",
+                name,
+                BUILTIN_NAMES.join(", "),
+                Self::templates_dir()
+                    .map(|dir| dir.to_str().unwrap().to_string())
+                    .unwrap_or_else(|| "~/.config/cproj/templates".to_string())
+            )
+            .into()
+        })
+    }
+
+    fn templates_dir() -> Option<PathBuf> {
+        config::user_dir().map(|dir| dir.join("templates"))
+    }
+
+    fn user_dir(name: &str) -> Option<PathBuf> {
+        Self::templates_dir()
+            .map(|dir| dir.join(name))
+            .filter(|dir| dir.is_dir())
+    }
+
+    // fills in the placeholders a user template can use
+    fn fill(text: &str, project_name: &str, lang: Lang) -> String {
+        text.replace("{{name}}", project_name)
+            .replace("{{lang}}", &lang.to_string())
+    }
+
+    // copies every file of the template, placeholders are filled in both the
+    // paths and the contents of text files
+    fn from_dir(dir: &path::Path, project_name: &str, lang: Lang) -> Result<Self> {
+        let mut files = vec![];
+        let mut directories = vec![dir.to_path_buf()];
+
+        while let Some(path) = directories.pop() {
+            let entries = fs::read_dir(&path)
+                .map_err(|_| format!("failed to read {}", path.to_str().unwrap()))?;
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    directories.push(path);
+                    continue;
+                }
+
+                let contents = fs::read(&path)
+                    .map_err(|_| format!("failed to read {}", path.to_str().unwrap()))?;
+                let contents = match String::from_utf8(contents) {
+                    Ok(text) => Self::fill(&text, project_name, lang).into_bytes(),
+                    Err(err) => err.into_bytes(),
+                };
+
+                let relative = path.strip_prefix(dir).unwrap().to_str().unwrap();
+                files.push((
+                    PathBuf::from(Self::fill(relative, project_name, lang)),
+                    contents,
+                ));
+            }
+        }
+
+        files.sort();
+
+        // a template can bring its own config, in either format, otherwise
+        // the default one is used
+        let mut config = None;
+        for format in config_file::Format::ALL {
+            let Some(i) = files
+                .iter()
+                .position(|(path, _)| path == path::Path::new(format.file_name()))
+            else {
+                continue;
+            };
+
+            if config.is_some() {
+                return Err("it has both a cproj.json and a cproj.toml".into());
+            }
+
+            let (_, contents) = files.remove(i);
+            let contents = String::from_utf8_lossy(&contents);
+            let mut parsed = format.parse(&contents).map_err(|err| {
+                err.prepend(&format!("failed to parse its {}: ", format.file_name()))
+            })?;
+
+            // like in new projects, a cc that is left out stays out so that
+            // the user config or CC can pick the compiler
+            if effective::lookup(&format.written_fields(&contents), "cc").is_none() {
+                parsed.cc.clear();
+            }
+            config = Some(parsed);
+        }
+        let config = config.unwrap_or_else(|| Config::new(project_name, lang));
+
+        Ok(Self { files, config })
+    }
+
+    fn builtin(name: &str, project_name: &str, lang: Lang) -> Option<Self> {
+        let ext = match lang {
+            Lang::C => "c",
+            Lang::Cpp => "cpp",
+        };
+        let src = |file: &str| PathBuf::from("src").join(format!("{}.{}", file, ext));
+
+        // the project name as it can be used in code
+        let ident: String = project_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let guard = format!("{}_H", ident.to_uppercase());
+
+        let header = format!(
+            "#ifndef {guard}\n#define {guard}\n\nint {ident}_add(int a, int b);\n\n#endif\n"
+        );
+        let lib_code = format!(
+            "#include <{project_name}.h>\n\nint {ident}_add(int a, int b)\n{{\n\treturn a + b;\n}}\n"
+        );
+
+        let mut config = Config::new(project_name, lang);
+
+        let files = match name {
+            "bin" => vec![(
+                src("main"),
+                "int main(int argc, char *argv[])\n{\n\treturn 0;\n}\n".to_string(),
+            )],
+            "lib" => {
+                config.kind = Kind::StaticLib;
+                vec![
+                    (PathBuf::from(format!("include/{}.h", project_name)), header),
+                    (src(project_name), lib_code),
+                ]
+            }
+            // cproj test runs a binary, so the library gets a small main that
+            // drives it for the cases
+            "lib+tests" => vec![
+                (PathBuf::from(format!("include/{}.h", project_name)), header),
+                (src(project_name), lib_code),
+                (
+                    src("main"),
+                    format!(
+                        "#include <stdio.h>\n#include <{project_name}.h>\n\n\
+                         // adds up each pair of numbers read from stdin, the cases in\n\
+                         // tests/cases are run against this\n\
+                         int main(void)\n{{\n\tint a, b;\n\n\
+                         \twhile (scanf(\"%d %d\", &a, &b) == 2)\n\
+                         \t\tprintf(\"%d\\n\", {ident}_add(a, b));\n\n\
+                         \treturn 0;\n}}\n"
+                    ),
+                ),
+                (PathBuf::from("tests/cases/add.in"), "1 2\n40 2\n".to_string()),
+                (PathBuf::from("tests/cases/add.out"), "3\n42\n".to_string()),
+            ],
+            "cli-with-args" => vec![
+                (
+                    src("main"),
+                    format!(
+                        "#include <stdio.h>\n#include <string.h>\n\n\
+                         static void print_usage(const char *program)\n{{\n\
+                         \tprintf(\"usage: %s [--help] [--version] [args...]\\n\", program);\n}}\n\n\
+                         int main(int argc, char *argv[])\n{{\n\
+                         \tfor (int i = 1; i < argc; i++) {{\n\
+                         \t\tif (strcmp(argv[i], \"-h\") == 0 || strcmp(argv[i], \"--help\") == 0) {{\n\
+                         \t\t\tprint_usage(argv[0]);\n\t\t\treturn 0;\n\
+                         \t\t}} else if (strcmp(argv[i], \"-v\") == 0 || strcmp(argv[i], \"--version\") == 0) {{\n\
+                         \t\t\tprintf(\"{project_name} 0.1.0\\n\");\n\t\t\treturn 0;\n\
+                         \t\t}} else if (argv[i][0] == '-') {{\n\
+                         \t\t\tfprintf(stderr, \"%s: unknown option %s\\n\", argv[0], argv[i]);\n\
+                         \t\t\tprint_usage(argv[0]);\n\t\t\treturn 2;\n\
+                         \t\t}}\n\n\
+                         \t\tprintf(\"%s\\n\", argv[i]);\n\t}}\n\n\
+                         \treturn 0;\n}}\n"
+                    ),
+                ),
+                (PathBuf::from("tests/cases/args.args"), "one two\n".to_string()),
+                (PathBuf::from("tests/cases/args.in"), String::new()),
+                (PathBuf::from("tests/cases/args.out"), "one\ntwo\n".to_string()),
+                (PathBuf::from("tests/cases/version.args"), "--version\n".to_string()),
+                (PathBuf::from("tests/cases/version.in"), String::new()),
+                (
+                    PathBuf::from("tests/cases/version.out"),
+                    format!("{} 0.1.0\n", project_name),
+                ),
+            ],
+            _ => return None,
+        };
+
+        Some(Self {
+            files: files
+                .into_iter()
+                .map(|(path, contents)| (path, contents.into_bytes()))
+                .collect(),
+            config,
+        })
+    }
+}
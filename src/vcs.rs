@@ -0,0 +1,40 @@
+use crate::arg_retriever::{ArgRetriever, ArgRule};
+
+/// The version control a new project is set up with
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Vcs {
+    Git,
+    None,
+}
+
+/// What git should ignore in a project, everything cproj builds or generates
+pub const GITIGNORE: &str = "\
+# build outputs of cproj
+/bin/
+# cproj package
+/dist/
+# the Makefile from cproj export make
+/build/
+# cproj install
+/install_manifest.txt
+";
+
+impl Vcs {
+    /// the argument rules used by [`Vcs::from_args()`]
+    pub fn arg_rules() -> Vec<ArgRule> {
+        vec![ArgRule::new("--vcs", 1)]
+    }
+
+    /// the vcs given on the command line along with whether it was given at
+    /// all, git is used when it wasnt. None if the vcs is unknown
+    pub fn from_args(arg_ret: &ArgRetriever) -> Option<(Self, bool)> {
+        match arg_ret.get_tag_args("--vcs") {
+            None => Some((Self::Git, false)),
+            Some(args) => match args[0].to_lowercase().as_str() {
+                "git" => Some((Self::Git, true)),
+                "none" => Some((Self::None, true)),
+                _ => None,
+            },
+        }
+    }
+}
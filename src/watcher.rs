@@ -0,0 +1,327 @@
+use std::{
+    collections::HashMap,
+    fs, path, thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// How long the watched files have to stay untouched before a burst of edits is
+/// considered over
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How often the polling fallback looks for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watches directories (recursively) and individual files for changes
+///
+/// Uses inotify where it is available and falls back to polling the mtimes of
+/// the watched files everywhere else
+///
+pub struct Watcher {
+    dirs: Vec<path::PathBuf>,
+    files: Vec<path::PathBuf>,
+    backend: Backend,
+}
+
+enum Backend {
+    #[cfg(target_os = "linux")]
+    Inotify(inotify::Inotify),
+    Poll(HashMap<path::PathBuf, SystemTime>),
+}
+
+impl Watcher {
+    pub fn new(dirs: Vec<path::PathBuf>, files: Vec<path::PathBuf>) -> Self {
+        #[cfg(target_os = "linux")]
+        let backend = match inotify::Inotify::new() {
+            Some(val) => Backend::Inotify(val),
+            None => Backend::Poll(HashMap::new()),
+        };
+
+        #[cfg(not(target_os = "linux"))]
+        let backend = Backend::Poll(HashMap::new());
+
+        let mut watcher = Self {
+            dirs: vec![],
+            files: vec![],
+            backend,
+        };
+
+        watcher.set_paths(dirs, files);
+        watcher
+    }
+
+    pub fn is_polling(&self) -> bool {
+        matches!(self.backend, Backend::Poll(_))
+    }
+
+    /// replaces what is being watched, such as when the include directories of
+    /// the config change
+    pub fn set_paths(&mut self, dirs: Vec<path::PathBuf>, files: Vec<path::PathBuf>) {
+        self.dirs = dirs;
+        self.files = files;
+        self.refresh();
+    }
+
+    /// blocks until something changes, then waits for the changes to settle
+    pub fn wait(&mut self) {
+        self.wait_for_change(None);
+        while self.wait_for_change(Some(DEBOUNCE)) {}
+
+        // directories may have been created while waiting
+        self.refresh();
+    }
+
+    // returns whether or not a change happened before the timeout
+    fn wait_for_change(&mut self, timeout: Option<Duration>) -> bool {
+        let start = Instant::now();
+
+        loop {
+            let changed = match &mut self.backend {
+                #[cfg(target_os = "linux")]
+                Backend::Inotify(inotify) => {
+                    let remaining = timeout.map(|val| val.saturating_sub(start.elapsed()));
+                    match inotify.read_events(remaining, &self.files) {
+                        Some(changed) => changed,
+                        // inotify stopped working, so try again by polling
+                        None => {
+                            self.backend = Backend::Poll(HashMap::new());
+                            self.refresh();
+                            false
+                        }
+                    }
+                }
+                Backend::Poll(snapshot) => {
+                    thread::sleep(POLL_INTERVAL.min(timeout.unwrap_or(POLL_INTERVAL)));
+                    let current = Self::snapshot(&self.dirs, &self.files);
+                    let changed = current != *snapshot;
+                    *snapshot = current;
+                    changed
+                }
+            };
+
+            if changed {
+                return true;
+            }
+
+            if timeout.is_some_and(|val| start.elapsed() >= val) {
+                return false;
+            }
+        }
+    }
+
+    fn refresh(&mut self) {
+        match &mut self.backend {
+            #[cfg(target_os = "linux")]
+            Backend::Inotify(inotify) => {
+                for dir in Self::walk_dirs(&self.dirs) {
+                    inotify.add_watch(&dir, true);
+                }
+
+                // files are watched through their parent directory so that
+                // editors which replace the file on save are still picked up
+                for file in &self.files {
+                    let parent = match file.parent() {
+                        Some(val) if !val.as_os_str().is_empty() => val.to_path_buf(),
+                        _ => path::PathBuf::from("."),
+                    };
+                    inotify.add_watch(&parent, false);
+                }
+            }
+            Backend::Poll(snapshot) => *snapshot = Self::snapshot(&self.dirs, &self.files),
+        }
+    }
+
+    // every directory inside of `dirs`, including `dirs` themselves
+    fn walk_dirs(dirs: &[path::PathBuf]) -> Vec<path::PathBuf> {
+        let mut found = vec![];
+        let mut to_visit: Vec<_> = dirs.iter().filter(|dir| dir.is_dir()).cloned().collect();
+
+        while let Some(dir) = to_visit.pop() {
+            if let Ok(entries) = fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    if entry.path().is_dir() {
+                        to_visit.push(entry.path());
+                    }
+                }
+            }
+
+            found.push(dir);
+        }
+
+        found
+    }
+
+    fn snapshot(
+        dirs: &[path::PathBuf],
+        files: &[path::PathBuf],
+    ) -> HashMap<path::PathBuf, SystemTime> {
+        let mut snapshot = HashMap::new();
+        let mut paths = files.to_vec();
+
+        for dir in Self::walk_dirs(dirs) {
+            if let Ok(entries) = fs::read_dir(&dir) {
+                paths.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        }
+
+        for path in paths {
+            if let Ok(mtime) = fs::metadata(&path).and_then(|meta| meta.modified()) {
+                snapshot.insert(path, mtime);
+            }
+        }
+
+        snapshot
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::{
+        collections::HashMap, ffi, io, mem, os::unix::ffi::OsStrExt, path, ptr, time::Duration,
+    };
+
+    const WATCH_MASK: u32 = libc::IN_MODIFY
+        | libc::IN_CLOSE_WRITE
+        | libc::IN_ATTRIB
+        | libc::IN_CREATE
+        | libc::IN_DELETE
+        | libc::IN_MOVED_FROM
+        | libc::IN_MOVED_TO;
+
+    pub struct Inotify {
+        fd: libc::c_int,
+        // watch descriptor -> watched directory
+        watches: HashMap<libc::c_int, path::PathBuf>,
+        // watched directory -> whether every change inside of it counts, as
+        // opposed to only changes to the watched files inside of it
+        recursive: HashMap<path::PathBuf, bool>,
+    }
+
+    impl Inotify {
+        pub fn new() -> Option<Self> {
+            // SAFETY: inotify_init1 has no preconditions
+            let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC | libc::IN_NONBLOCK) };
+
+            if fd < 0 {
+                None
+            } else {
+                Some(Self {
+                    fd,
+                    watches: HashMap::new(),
+                    recursive: HashMap::new(),
+                })
+            }
+        }
+
+        pub fn add_watch(&mut self, dir: &path::Path, recursive: bool) {
+            let c_path = match ffi::CString::new(dir.as_os_str().as_bytes()) {
+                Ok(val) => val,
+                Err(_) => return,
+            };
+
+            // SAFETY: c_path is a valid nul terminated string, adding a watch
+            // for a directory that is already watched returns its existing
+            // descriptor
+            let wd = unsafe { libc::inotify_add_watch(self.fd, c_path.as_ptr(), WATCH_MASK) };
+
+            if wd >= 0 {
+                self.watches.insert(wd, dir.to_path_buf());
+                *self.recursive.entry(dir.to_path_buf()).or_insert(false) |= recursive;
+            }
+        }
+
+        /// Waits for events, returns whether any of them were relevant or None if
+        /// inotify failed. Events in the parent directories of `files` only
+        /// count if they are about one of `files`
+        pub fn read_events(
+            &mut self,
+            timeout: Option<Duration>,
+            files: &[path::PathBuf],
+        ) -> Option<bool> {
+            let mut poll_fd = libc::pollfd {
+                fd: self.fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let timeout = timeout.map_or(-1, |val| val.as_millis() as libc::c_int);
+
+            // SAFETY: poll_fd is a single valid pollfd
+            let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout) };
+
+            if ready < 0 {
+                // being interrupted by a signal doesnt mean inotify broke
+                let interrupted = io::Error::last_os_error().kind() == io::ErrorKind::Interrupted;
+                return if interrupted { Some(false) } else { None };
+            } else if ready == 0 {
+                return Some(false);
+            }
+
+            let mut buffer = [0u8; 4096];
+            let mut changed = false;
+
+            loop {
+                // SAFETY: the buffer is valid for writes of its length
+                let len = unsafe {
+                    libc::read(
+                        self.fd,
+                        buffer.as_mut_ptr() as *mut libc::c_void,
+                        buffer.len(),
+                    )
+                };
+
+                if len <= 0 {
+                    break;
+                }
+
+                let mut offset = 0;
+                while offset + mem::size_of::<libc::inotify_event>() <= len as usize {
+                    // SAFETY: the kernel only writes whole events into the
+                    // buffer, the read is unaligned since the buffer is a byte
+                    // array
+                    let event: libc::inotify_event =
+                        unsafe { ptr::read_unaligned(buffer.as_ptr().add(offset) as *const _) };
+
+                    let name_start = offset + mem::size_of::<libc::inotify_event>();
+                    let name_bytes = &buffer[name_start..name_start + event.len as usize];
+                    let name_len = name_bytes
+                        .iter()
+                        .position(|byte| *byte == 0)
+                        .unwrap_or(name_bytes.len());
+                    let name = ffi::OsStr::from_bytes(&name_bytes[..name_len]);
+
+                    if let Some(dir) = self.watches.get(&event.wd) {
+                        if self.is_relevant(dir, name, files) {
+                            changed = true;
+                        }
+                    }
+
+                    offset = name_start + event.len as usize;
+                }
+            }
+
+            Some(changed)
+        }
+
+        fn is_relevant(
+            &self,
+            dir: &path::Path,
+            name: &ffi::OsStr,
+            files: &[path::PathBuf],
+        ) -> bool {
+            let normalize =
+                |path: &path::Path| path.strip_prefix(".").unwrap_or(path).to_path_buf();
+            let path = normalize(&dir.join(name));
+
+            self.recursive.get(dir).copied().unwrap_or(false)
+                || files.iter().any(|file| normalize(file) == path)
+        }
+    }
+
+    impl Drop for Inotify {
+        fn drop(&mut self) {
+            // SAFETY: fd is owned by this struct and only closed here
+            unsafe {
+                libc::close(self.fd);
+            }
+        }
+    }
+}